@@ -13,6 +13,8 @@ pub fn reformat(f: &Path)
 {
     let rustfmt_conf = ws_path!("rustfmt.toml");
 
+    // `cmd!` expands to a cfg only rust-analyzer sets.
+    #[allow(unexpected_cfgs)]
     let cmd =
         xshell::cmd!("rustfmt --edition=2021 --config-path {rustfmt_conf} {f}");
 
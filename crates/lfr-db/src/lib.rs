@@ -9,13 +9,14 @@ use lfr_vfs::FileWatcher;
 #[salsa::database(lfr_vfs::VfsDatabaseStorage,
                   lfr_hir_def::db::HirDefStorage,
                   lfr_hir_def::db::InternDatabaseStorage)]
+#[allow(dead_code)] // nothing opens the database yet
 struct LfrDatabase
 {
     storage: salsa::Storage<Self>,
     watcher: lfr_vfs::VfsWatcher,
 }
 
-impl<'a> salsa::Database for LfrDatabase {}
+impl salsa::Database for LfrDatabase {}
 
 impl FileWatcher for LfrDatabase
 {
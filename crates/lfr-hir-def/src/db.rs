@@ -1,6 +1,8 @@
 use lfr_base_db::salsa;
 
 use crate::hir::{
+    Enum,
+    EnumData,
     Field,
     FieldData,
     Struct,
@@ -20,6 +22,8 @@ pub trait InternDatabase: lfr_vfs::VfsDatabase
     #[salsa::interned]
     fn intern_struct(&self, data: StructData) -> Struct;
     #[salsa::interned]
+    fn intern_enum(&self, data: EnumData) -> Enum;
+    #[salsa::interned]
     fn intern_field(&self, data: FieldData) -> Field;
     #[salsa::interned]
     fn intern_type_ref(&self, data: TypeRefData) -> TypeRef;
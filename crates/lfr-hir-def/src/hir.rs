@@ -7,12 +7,13 @@ use lfr_base_db::{
 
 use crate::db;
 
-trait Intern
+pub(crate) trait Intern
 {
     type ID;
     fn intern(self, db: &dyn db::DefDatabase) -> Self::ID;
 }
 
+#[allow(dead_code)] // only the tests read the interned data back yet
 pub trait Lookup
 {
     type Data;
@@ -55,5 +56,6 @@ macro_rules! include_intern {
 }
 
 include_intern!(pub struct_, "hir/struct.rs");
+include_intern!(pub enum_, "hir/enum.rs");
 include_intern!(pub field, "hir/field.rs");
 include_intern!(pub type_ref, "hir/type_ref.rs");
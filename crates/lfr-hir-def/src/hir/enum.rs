@@ -0,0 +1,12 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Enum(salsa::InternId);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EnumData {
+    pub name: Arc<String>,
+    pub variants: Arc<Vec<Arc<String>>>,
+}
+
+impl_intern!(Enum, EnumData, intern_enum, lookup_intern_enum);
@@ -1,3 +1,6 @@
 pub mod db;
 mod hir;
 pub mod lower_syntax;
+
+#[cfg(test)]
+mod tests;
@@ -1,6 +1,24 @@
-use lfr_syntax::ast;
+use std::sync::Arc;
+
+use lfr_syntax::{
+    SyntaxToken,
+    ast,
+    ident,
+};
 
 use crate::db::DefDatabase;
+use crate::hir::{
+    Enum,
+    EnumData,
+    Field,
+    FieldData,
+    Intern,
+    Path,
+    Struct,
+    StructData,
+    TypeRef,
+    TypeRefData,
+};
 
 pub fn lower_root(root: &ast::Root, db: &dyn DefDatabase)
 {
@@ -11,42 +29,191 @@ pub fn lower_root(root: &ast::Root, db: &dyn DefDatabase)
 
 pub fn lower_stmt(stmt: &ast::Stmt, db: &dyn DefDatabase)
 {
-    match stmt {
-        ast::Stmt::ExprStmt(e) => {
-            let lower = lower_expr(&e.expr().unwrap(), db);
+    let inner = match stmt.inner() {
+        Some(inner) => inner,
+        None => {
+            if let Some(expr) = stmt.expr() {
+                lower_expr(&expr, db);
+            }
+            return
+        }
+    };
+
+    match inner {
+        ast::StmtInner::ExprStmt(e) => {
+            lower_expr(&e.expr().unwrap(), db);
+        }
+        ast::StmtInner::DeclarationStmt(_) => todo!(),
+        ast::StmtInner::WhileStmt(_) => todo!(),
+        ast::StmtInner::ForStmt(_) => todo!(),
+        ast::StmtInner::StructDef(s) => {
+            lower_struct_def(&s, db);
+        }
+        ast::StmtInner::EnumDef(e) => {
+            lower_enum_def(&e, db);
         }
-        ast::Stmt::DeclarationStmt(d) => todo!(),
-        ast::Stmt::WhileStmt(_) => todo!(),
-        ast::Stmt::ForStmt(_) => todo!(),
     }
 }
 
+pub fn lower_struct_def(def: &ast::StructDef,
+                        db: &dyn DefDatabase)
+                        -> Option<Struct>
+{
+    let name = ident_text(&def.ident_token()?);
+    let fields = def.field_list()
+                    .into_iter()
+                    .flat_map(|it| it.field_defs())
+                    .filter_map(|it| lower_field_def(&it, db))
+                    .collect();
+
+    Some(StructData { name:   Arc::new(name),
+                      fields: Arc::new(fields), }.intern(db))
+}
+
+pub fn lower_field_def(def: &ast::FieldDef,
+                       db: &dyn DefDatabase)
+                       -> Option<Field>
+{
+    let name = ident_text(&def.ident_token()?);
+    let ty = lower_type_ref(&def.type_ref()?, db)?;
+
+    Some(FieldData { name: Arc::new(name),
+                     ty }.intern(db))
+}
+
+pub fn lower_enum_def(def: &ast::EnumDef, db: &dyn DefDatabase) -> Option<Enum>
+{
+    let name = ident_text(&def.ident_token()?);
+    let variants = def.variant_list()
+                      .into_iter()
+                      .flat_map(|it| it.variants())
+                      .filter_map(|it| it.ident_token())
+                      .map(|it| Arc::new(ident_text(&it)))
+                      .collect();
+
+    Some(EnumData { name:     Arc::new(name),
+                    variants: Arc::new(variants), }.intern(db))
+}
+
 pub fn lower_expr(expr: &ast::Expr, db: &dyn DefDatabase)
 {
     match expr {
-        ast::Expr::PrimaryExpr(pe) => match pe.inner().unwrap() {
-            ast::PrimaryExprInner::TupleExpr(_) => todo!(),
-            ast::PrimaryExprInner::ArrExpr(_) => todo!(),
-            ast::PrimaryExprInner::Block(_) => todo!(),
-            ast::PrimaryExprInner::LitVal(lv) => {
-                match lv.inner().unwrap() {
-                    ast::LitValInner::StringLit(slit) => slit.str_token(),
-                    ast::LitValInner::NumberLit(_) => todo!(),
-                    ast::LitValInner::BooleanLit(_) => todo!(),
-                };
+        ast::Expr::PrimaryExpr(pe) => {
+            let inner = match pe.inner() {
+                Some(inner) => inner,
+                // a name or `this`, there's nothing in it
+                None => return,
+            };
+            match inner {
+                ast::PrimaryExprInner::TupleExpr(_) => todo!(),
+                ast::PrimaryExprInner::ArrExpr(_) => todo!(),
+                ast::PrimaryExprInner::MapExpr(m) => {
+                    for entry in m.map_entrys() {
+                        lower_opt_expr(entry.key(), db);
+                        lower_opt_expr(entry.value(), db);
+                    }
+                }
+                ast::PrimaryExprInner::Block(_) => todo!(),
+                ast::PrimaryExprInner::LitVal(lv) => {
+                    match lv.inner().unwrap() {
+                        ast::LitValInner::StringLit(slit) => slit.str_token(),
+                        ast::LitValInner::NumberLit(_) => todo!(),
+                        ast::LitValInner::CharLit(clit) => clit.char_token(),
+                        ast::LitValInner::BooleanLit(_) => todo!(),
+                    };
+                }
+                ast::PrimaryExprInner::InterpolatedStr(s) => {
+                    for expr in s.exprs() {
+                        lower_expr(&expr, db);
+                    }
+                }
+                ast::PrimaryExprInner::IfExpr(_) => todo!(),
+                ast::PrimaryExprInner::MatchExpr(m) => {
+                    lower_opt_expr(m.expr(), db);
+                    let arms = m.match_arm_list()
+                                .into_iter()
+                                .flat_map(|it| it.match_arms());
+                    for arm in arms {
+                        lower_opt_expr(arm.expr(), db);
+                    }
+                }
+                ast::PrimaryExprInner::BreakStmt(_) => todo!(),
+                ast::PrimaryExprInner::ContinueStmt(_) => todo!(),
+                ast::PrimaryExprInner::ReturnStmt(_) => todo!(),
+                ast::PrimaryExprInner::Path(_) => todo!(),
             }
-            ast::PrimaryExprInner::IfExpr(_) => todo!(),
-            ast::PrimaryExprInner::BreakStmt(_) => todo!(),
-            ast::PrimaryExprInner::ContinueStmt(_) => todo!(),
-            ast::PrimaryExprInner::ReturnStmt(_) => todo!(),
-            ast::PrimaryExprInner::Path(_) => todo!(),
-        },
+        }
         ast::Expr::BinExpr(_) => todo!(),
+        ast::Expr::RangeExpr(r) => {
+            lower_opt_expr(r.start(), db);
+            lower_opt_expr(r.end(), db);
+        }
+        ast::Expr::PipeExpr(p) => {
+            lower_opt_expr(p.lhs(), db);
+            lower_opt_expr(p.rhs(), db);
+        }
+        ast::Expr::TernaryExpr(t) => {
+            lower_opt_expr(t.cond(), db);
+            lower_opt_expr(t.then_expr(), db);
+            lower_opt_expr(t.else_expr(), db);
+        }
         ast::Expr::PrefixUnaryExpr(_) => todo!(),
         ast::Expr::FnCallExpr(_) => todo!(),
         ast::Expr::IndexExpr(_) => todo!(),
         ast::Expr::MemberAccessExpr(_) => todo!(),
         ast::Expr::MethodCallExpr(_) => todo!(),
         ast::Expr::FnDef(_) => todo!(),
+        ast::Expr::LambdaExpr(l) => lower_opt_expr(l.body(), db),
+    }
+}
+
+fn lower_opt_expr(expr: Option<ast::Expr>, db: &dyn DefDatabase)
+{
+    if let Some(expr) = expr {
+        lower_expr(&expr, db);
+    }
+}
+
+pub fn lower_type_ref(type_ref: &ast::TypeRef,
+                      db: &dyn DefDatabase)
+                      -> Option<TypeRef>
+{
+    let data = if type_ref.bang_token().is_some() {
+        TypeRefData::Never
     }
+    else if type_ref.underscore_token().is_some() {
+        TypeRefData::Placeholder
+    }
+    else {
+        let path = lower_path(&type_ref.path()?);
+        match type_ref.generic_arg_list() {
+            Some(args) => {
+                let ty_args = args.type_refs()
+                                  .filter_map(|it| lower_type_ref(&it, db))
+                                  .collect();
+                TypeRefData::Generic { path, ty_args }
+            }
+            None => TypeRefData::Path(path),
+        }
+    };
+
+    Some(data.intern(db))
+}
+
+pub fn lower_path(path: &ast::Path) -> Path
+{
+    let frags = path.path_frags().filter_map(|it| it.ident_token());
+    let segments = path.ident_token()
+                       .into_iter()
+                       .chain(frags)
+                       .map(|it| Arc::new(ident_text(&it)))
+                       .collect();
+    Path { segments }
+}
+
+/// The text of an identifier, in NFC so that spellings of a name compare
+/// equal.
+fn ident_text(token: &SyntaxToken) -> String
+{
+    ident::normalize(token.text()).into_owned()
 }
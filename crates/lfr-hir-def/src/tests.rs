@@ -0,0 +1,184 @@
+//! Lowers snippets of source and reads back what got interned.
+
+use std::path::{
+    Path,
+    PathBuf,
+};
+
+use lfr_base_db::salsa;
+use lfr_base_db::salsa::debug::DebugQueryTable;
+use lfr_parser::parser::parse;
+use lfr_syntax::SyntaxNode;
+use lfr_syntax::ast::{
+    self,
+    AstNode,
+};
+use lfr_vfs::FileWatcher;
+
+use crate::db::InternStructQuery;
+use crate::hir::{
+    Lookup,
+    StructData,
+    TypeRef,
+    TypeRefData,
+};
+use crate::lower_syntax::lower_root;
+
+#[salsa::database(lfr_vfs::VfsDatabaseStorage,
+                  crate::db::HirDefStorage,
+                  crate::db::InternDatabaseStorage)]
+#[derive(Default)]
+struct TestDb
+{
+    storage: salsa::Storage<Self>,
+}
+
+impl salsa::Database for TestDb {}
+
+impl FileWatcher for TestDb
+{
+    fn watch(&self, _: &Path) {}
+
+    fn did_change_file(&mut self, _: &PathBuf) {}
+}
+
+fn lower(db: &TestDb, text: &str)
+{
+    let (green, errors) = parse(text);
+    assert!(errors.is_empty(), "{:?}", errors);
+    lower_root(&ast::Root::cast(SyntaxNode::new_root(green)).unwrap(),
+               db);
+}
+
+/// Every struct interned so far.
+fn structs(db: &TestDb) -> Vec<StructData>
+{
+    InternStructQuery.in_db(db)
+                     .entries::<Vec<_>>()
+                     .into_iter()
+                     .map(|it| it.value.unwrap().lookup(db))
+                     .collect()
+}
+
+/// A type as it would be written in source.
+fn render_type(db: &TestDb, ty: TypeRef) -> String
+{
+    let render_path = |path: &crate::hir::Path| {
+        let segments: Vec<_> =
+            path.segments.iter().map(|it| it.as_str()).collect();
+        segments.join("::")
+    };
+
+    match ty.lookup(db) {
+        TypeRefData::Never => "!".to_string(),
+        TypeRefData::Placeholder => "_".to_string(),
+        TypeRefData::Path(path) => render_path(&path),
+        TypeRefData::Generic { path, ty_args } => {
+            let args: Vec<_> =
+                ty_args.into_iter().map(|it| render_type(db, it)).collect();
+            format!("{}<{}>",
+                    render_path(&path),
+                    args.join(", "))
+        }
+    }
+}
+
+/// The fields of `data`, as `name: type`.
+fn render_fields(db: &TestDb, data: &StructData) -> Vec<String>
+{
+    data.fields
+        .iter()
+        .map(|it| it.lookup(db))
+        .map(|it| {
+            format!("{}: {}",
+                    it.name,
+                    render_type(db, it.ty))
+        })
+        .collect()
+}
+
+#[test]
+fn struct_defs_intern_with_their_fields()
+{
+    let db = TestDb::default();
+    lower(&db,
+          "struct Target {\n  name: str,\n  os: Os,\n}\n");
+
+    let structs = structs(&db);
+    assert_eq!(structs.len(), 1);
+    assert_eq!(*structs[0].name, "Target");
+
+    assert_eq!(render_fields(&db, &structs[0]),
+               ["name: str", "os: Os"]);
+}
+
+#[test]
+fn field_types_intern()
+{
+    let db = TestDb::default();
+    lower(&db,
+          "struct S { a: _, b: !, c: ::std::path, d: map<str, list<_>> }");
+
+    let structs = structs(&db);
+    assert_eq!(structs.len(), 1);
+    assert_eq!(render_fields(&db, &structs[0]),
+               ["a: _", "b: !", "c: std::path", "d: map<str, list<_>>"]);
+
+    // both `_`s are the same interned type.
+    let fields: Vec<_> = structs[0].fields
+                                   .iter()
+                                   .map(|it| it.lookup(&db).ty)
+                                   .collect();
+    let list = match fields[3].lookup(&db) {
+        TypeRefData::Generic { ty_args, .. } => ty_args[1],
+        _ => unreachable!(),
+    };
+    match list.lookup(&db) {
+        TypeRefData::Generic { ty_args, .. } => {
+            assert_eq!(ty_args, [fields[0]])
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn maps_lower()
+{
+    lower(&TestDb::default(),
+          "%{ 'a': b, 'c': %{ this: 'd' } }\n");
+}
+
+#[test]
+fn lambdas_lower()
+{
+    lower(&TestDb::default(),
+          "|a, b| %{ 'k': |c| b }\n");
+}
+
+#[test]
+fn matches_lower()
+{
+    lower(&TestDb::default(),
+          "match x { 'a' => %{}, _ => |y| y }\n");
+}
+
+#[test]
+fn ranges_and_ternaries_lower()
+{
+    lower(&TestDb::default(),
+          "a ? b..c : %{ 'k': d ? e..=f : g }\n");
+}
+
+#[test]
+fn pipes_lower()
+{
+    lower(&TestDb::default(),
+          "srcs |> sort\n    |> |it| it ? a..b : %{}\n");
+}
+
+#[test]
+fn char_lits_lower()
+{
+    lower(&TestDb::default(),
+          "c'a'\nc'\\r'\nc'\\\"'\nc'\\u{1F600}'\n");
+}
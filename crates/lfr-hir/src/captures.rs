@@ -0,0 +1,221 @@
+//! Finding the names a lambda captures from around it.
+
+use crate::{
+    HirBlock,
+    HirDecl,
+    HirExpr,
+    HirFnArg,
+    HirFnDecl,
+    HirName,
+    HirPat,
+    HirStmt,
+};
+
+/// The names `body` uses that neither `params` nor a `let`, `for`, function
+/// or type within `body` binds, each at its first use. Assigning to a name
+/// with `=` uses it, only `let` binds a new one.
+pub(crate) fn captures<'db>(params: &[HirFnArg<'db>],
+                            body: &HirExpr<'db>)
+                            -> Vec<HirName<'db>>
+{
+    let mut walker = Walker { scopes:   vec![],
+                              captures: vec![], };
+    walker.args(params, |w| w.expr(body));
+    walker.captures
+}
+
+struct Walker<'a, 'db>
+{
+    /// The names bound so far in each enclosing scope, innermost last.
+    scopes:   Vec<Vec<&'a str>>,
+    captures: Vec<HirName<'db>>,
+}
+
+impl<'a, 'db> Walker<'a, 'db>
+{
+    fn scope(&mut self, f: impl FnOnce(&mut Self))
+    {
+        self.scopes.push(vec![]);
+        f(self);
+        self.scopes.pop();
+    }
+
+    fn bind(&mut self, name: &'a HirName<'db>)
+    {
+        self.scopes
+            .last_mut()
+            .expect("names are bound within a scope")
+            .push(name);
+    }
+
+    fn use_name(&mut self, name: &HirName<'db>)
+    {
+        let bound = self.scopes.iter().flatten().any(|&it| it == &**name);
+        if !bound && !self.captures.contains(name) {
+            self.captures.push(name.clone());
+        }
+    }
+
+    /// Walks the defaults of `args`, which don't see the other arguments,
+    /// then `f` in a scope with the arguments bound.
+    fn args(&mut self, args: &'a [HirFnArg<'db>], f: impl FnOnce(&mut Self))
+    {
+        for default in args.iter().filter_map(|it| it.default.as_ref()) {
+            self.expr(default);
+        }
+        self.scope(|w| {
+                for arg in args {
+                    w.bind(&arg.name);
+                }
+                f(w)
+            })
+    }
+
+    fn fn_decl(&mut self, decl: &'a HirFnDecl<'db>)
+    {
+        self.args(&decl.args, |w| w.block(&decl.body))
+    }
+
+    fn block(&mut self, block: &'a HirBlock<'db>)
+    {
+        self.scope(|w| {
+                for stmt in &block.stmts {
+                    w.stmt(stmt);
+                }
+            })
+    }
+
+    fn stmt(&mut self, stmt: &'a HirStmt<'db>)
+    {
+        match stmt {
+            HirStmt::Expr { expr, .. } => self.expr(expr),
+            HirStmt::Decl(HirDecl::Struct(it)) => self.bind(&it.name),
+            HirStmt::Decl(HirDecl::Enum(it)) => self.bind(&it.name),
+            HirStmt::Decl(HirDecl::Fn(it)) => {
+                self.bind(&it.name);
+                self.fn_decl(it);
+            }
+            HirStmt::Let { name, value, .. } => {
+                self.expr(value);
+                self.bind(name);
+            }
+            HirStmt::While { cond, body, .. } => {
+                self.expr(cond);
+                self.block(body);
+            }
+            HirStmt::For { pat,
+                           iterable,
+                           body,
+                           .. } => {
+                self.expr(iterable);
+                self.scope(|w| {
+                        w.pat(pat);
+                        w.block(body)
+                    })
+            }
+        }
+    }
+
+    fn pat(&mut self, pat: &'a HirPat<'db>)
+    {
+        match pat {
+            HirPat::Missing
+            | HirPat::Wildcard
+            | HirPat::Lit(_)
+            | HirPat::Path(_) => {}
+            HirPat::Bind(name) => self.bind(name),
+            HirPat::Tuple(pats) => {
+                for pat in pats {
+                    self.pat(pat);
+                }
+            }
+        }
+    }
+
+    fn expr(&mut self, expr: &'a HirExpr<'db>)
+    {
+        match expr {
+            HirExpr::Missing | HirExpr::Lit(_) | HirExpr::Continue => {}
+            HirExpr::Name(name) => self.use_name(name),
+            // `Os::Linux` uses the enum.
+            HirExpr::Path(path) => {
+                if let Some(name) = path.first() {
+                    self.use_name(name);
+                }
+            }
+            HirExpr::InterpolatedStr(exprs)
+            | HirExpr::Tuple(exprs)
+            | HirExpr::Array(exprs) => {
+                for expr in exprs {
+                    self.expr(expr);
+                }
+            }
+            HirExpr::Map(entries) => {
+                for (key, value) in entries {
+                    self.expr(key);
+                    self.expr(value);
+                }
+            }
+            HirExpr::Block(block) => self.block(block),
+            HirExpr::Prefix { expr, .. } => self.expr(expr),
+            HirExpr::Binary { lhs, rhs, .. } => {
+                self.expr(lhs);
+                self.expr(rhs);
+            }
+            HirExpr::Call { callee, args } => {
+                self.expr(callee);
+                for arg in args {
+                    self.expr(&arg.value);
+                }
+            }
+            HirExpr::MethodCall { receiver, args, .. } => {
+                self.expr(receiver);
+                for arg in args {
+                    self.expr(&arg.value);
+                }
+            }
+            HirExpr::Index { base, index } => {
+                self.expr(base);
+                self.expr(index);
+            }
+            HirExpr::Member { base, .. } => self.expr(base),
+            HirExpr::If { branches,
+                          else_branch, } => {
+                for (cond, block) in branches {
+                    self.expr(cond);
+                    self.block(block);
+                }
+                if let Some(block) = else_branch {
+                    self.block(block);
+                }
+            }
+            HirExpr::Match { scrutinee, arms, .. } => {
+                self.expr(scrutinee);
+                for arm in arms {
+                    self.scope(|w| {
+                            w.pat(&arm.pat);
+                            w.expr(&arm.expr)
+                        })
+                }
+            }
+            // a function is callable from the rest of the block, and from
+            // itself.
+            HirExpr::Fn(decl) => {
+                self.bind(&decl.name);
+                self.fn_decl(decl);
+            }
+            // what a nested lambda captures and isn't bound here is captured
+            // from further out.
+            HirExpr::Lambda { captures, .. } => {
+                for name in captures {
+                    self.use_name(name);
+                }
+            }
+            HirExpr::Break(expr) | HirExpr::Return(expr) => {
+                if let Some(expr) = expr {
+                    self.expr(expr);
+                }
+            }
+        }
+    }
+}
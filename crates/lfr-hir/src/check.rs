@@ -0,0 +1,410 @@
+//! Checks over lowered code that need no types.
+
+use std::fmt;
+
+use crate::{
+    HirAttr,
+    HirBlock,
+    HirDecl,
+    HirEnumDecl,
+    HirExpr,
+    HirFnArg,
+    HirFnDecl,
+    HirLit,
+    HirMatchArm,
+    HirName,
+    HirPat,
+    HirStmt,
+    Span,
+};
+
+/// A problem found in lowered code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HirDiagnostic<'db>
+{
+    /// A `match` over the enum `enum_name` without an arm for the variants
+    /// `missing`, nor one that matches anything.
+    NonExhaustiveMatch
+    {
+        span:      Span,
+        enum_name: &'db str,
+        missing:   Vec<&'db str>,
+    },
+    /// A use of `name`, declared `@deprecated(note)`.
+    Deprecated
+    {
+        span: Span,
+        name: &'db str,
+        note: Option<String>,
+    },
+}
+
+impl<'db> HirDiagnostic<'db>
+{
+    /// Where the problem is.
+    pub fn span(&self) -> Span
+    {
+        match self {
+            HirDiagnostic::NonExhaustiveMatch { span, .. }
+            | HirDiagnostic::Deprecated { span, .. } => *span,
+        }
+    }
+}
+
+impl<'db> fmt::Display for HirDiagnostic<'db>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            HirDiagnostic::NonExhaustiveMatch { enum_name, missing, .. } => {
+                write!(f, "non-exhaustive match, missing ")?;
+                for (i, variant) in missing.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "`{}::{}`", enum_name, variant)?;
+                }
+                Ok(())
+            }
+            HirDiagnostic::Deprecated { name, note, .. } => {
+                write!(f, "use of deprecated `{}`", name)?;
+                if let Some(note) = note {
+                    write!(f, ": {}", note)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Checks `stmts`, the statements of a file.
+///
+/// A `match` is taken to be over an enum when it matches over a variant of
+/// an enum declared in an enclosing block, as in `Os::Linux`, or over a name
+/// a `let` bound to one. Failing that, when one of its arms names such a
+/// variant. Variants nested in tuple patterns aren't looked at.
+///
+/// Uses of a name declared `@deprecated` are reported where the name is used
+/// as an expression, not in types or patterns.
+pub fn check_stmts<'a>(stmts: &'a [HirStmt<'_>]) -> Vec<HirDiagnostic<'a>>
+{
+    let mut checker = Checker { scopes:      vec![],
+                                diagnostics: vec![], };
+    checker.stmts(stmts);
+    checker.diagnostics
+}
+
+/// What the checks need to know about a name in scope.
+#[derive(Clone, Copy)]
+struct Binding<'a, 'db>
+{
+    name:       &'a str,
+    enum_decl:  Option<&'a HirEnumDecl<'db>>,
+    /// The enum of the variant a `let` bound the name to.
+    variant_of: Option<&'a HirEnumDecl<'db>>,
+    /// The `@deprecated` attribute of the declaration.
+    deprecated: Option<&'a HirAttr<'db>>,
+}
+
+impl<'a, 'db> Binding<'a, 'db>
+{
+    fn new(name: &'a HirName<'db>, attrs: &'a [HirAttr<'db>]) -> Self
+    {
+        Binding { name,
+                  enum_decl:  None,
+                  variant_of: None,
+                  deprecated: HirAttr::find(attrs, "deprecated"), }
+    }
+}
+
+struct Checker<'a, 'db>
+{
+    /// The names bound in each enclosing scope, innermost last.
+    scopes:      Vec<Vec<Binding<'a, 'db>>>,
+    diagnostics: Vec<HirDiagnostic<'a>>,
+}
+
+impl<'a, 'db> Checker<'a, 'db>
+{
+    fn scope(&mut self, f: impl FnOnce(&mut Self))
+    {
+        self.scopes.push(vec![]);
+        f(self);
+        self.scopes.pop();
+    }
+
+    fn bind(&mut self, binding: Binding<'a, 'db>)
+    {
+        self.scopes
+            .last_mut()
+            .expect("names are bound within a scope")
+            .push(binding);
+    }
+
+    fn stmts(&mut self, stmts: &'a [HirStmt<'db>])
+    {
+        self.scope(|c| {
+                // declarations are visible in all of their block
+                for stmt in stmts {
+                    match stmt {
+                        HirStmt::Decl(HirDecl::Enum(it)) => {
+                            c.bind(Binding { enum_decl: Some(it),
+                                             ..Binding::new(&it.name,
+                                                            &it.attrs) })
+                        }
+                        HirStmt::Decl(HirDecl::Struct(it)) => {
+                            c.bind(Binding::new(&it.name, &it.attrs))
+                        }
+                        HirStmt::Decl(HirDecl::Fn(it)) => {
+                            c.bind(Binding::new(&it.name, &it.attrs))
+                        }
+                        HirStmt::Expr { expr: HirExpr::Fn(it),
+                                        .. } => {
+                            c.bind(Binding::new(&it.name, &it.attrs))
+                        }
+                        _ => {}
+                    }
+                }
+                for stmt in stmts {
+                    c.stmt(stmt);
+                }
+            })
+    }
+
+    fn block(&mut self, block: &'a HirBlock<'db>) { self.stmts(&block.stmts) }
+
+    /// Checks the defaults of `args`, then `f` in a scope with the arguments
+    /// bound.
+    fn args(&mut self, args: &'a [HirFnArg<'db>], f: impl FnOnce(&mut Self))
+    {
+        for default in args.iter().filter_map(|it| it.default.as_ref()) {
+            self.expr(default);
+        }
+        self.scope(|c| {
+                for arg in args {
+                    c.bind(Binding::new(&arg.name, &[]));
+                }
+                f(c)
+            })
+    }
+
+    fn fn_decl(&mut self, decl: &'a HirFnDecl<'db>)
+    {
+        self.args(&decl.args, |c| c.block(&decl.body))
+    }
+
+    fn stmt(&mut self, stmt: &'a HirStmt<'db>)
+    {
+        match stmt {
+            HirStmt::Expr { expr, .. } => self.expr(expr),
+            HirStmt::Let { attrs, name, value, .. } => {
+                self.expr(value);
+                let variant_of = self.enum_of(value);
+                self.bind(Binding { variant_of,
+                                    ..Binding::new(name, attrs) });
+            }
+            HirStmt::Decl(HirDecl::Fn(decl)) => self.fn_decl(decl),
+            HirStmt::Decl(HirDecl::Struct(_) | HirDecl::Enum(_)) => {}
+            HirStmt::While { cond, body, .. } => {
+                self.expr(cond);
+                self.block(body);
+            }
+            HirStmt::For { pat,
+                           iterable,
+                           body,
+                           .. } => {
+                self.expr(iterable);
+                self.scope(|c| {
+                        c.pat(pat);
+                        c.block(body)
+                    })
+            }
+        }
+    }
+
+    fn pat(&mut self, pat: &'a HirPat<'db>)
+    {
+        match pat {
+            HirPat::Missing
+            | HirPat::Wildcard
+            | HirPat::Lit(_)
+            | HirPat::Path(_) => {}
+            HirPat::Bind(name) => self.bind(Binding::new(name, &[])),
+            HirPat::Tuple(pats) => {
+                for pat in pats {
+                    self.pat(pat);
+                }
+            }
+        }
+    }
+
+    fn expr(&mut self, expr: &'a HirExpr<'db>)
+    {
+        match expr {
+            HirExpr::Missing | HirExpr::Lit(_) | HirExpr::Continue => {}
+            HirExpr::Name(name) => self.check_deprecated(name),
+            HirExpr::Path(path) => {
+                if let Some(name) = path.first() {
+                    self.check_deprecated(name);
+                }
+            }
+            HirExpr::InterpolatedStr(exprs)
+            | HirExpr::Tuple(exprs)
+            | HirExpr::Array(exprs) => {
+                for expr in exprs {
+                    self.expr(expr);
+                }
+            }
+            HirExpr::Map(entries) => {
+                for (key, value) in entries {
+                    self.expr(key);
+                    self.expr(value);
+                }
+            }
+            HirExpr::Block(block) => self.block(block),
+            HirExpr::Prefix { expr, .. }
+            | HirExpr::Member { base: expr, .. } => self.expr(expr),
+            HirExpr::Lambda { params, body, .. } => {
+                self.args(params, |c| c.expr(body))
+            }
+            HirExpr::Binary { lhs, rhs, .. }
+            | HirExpr::Index { base: lhs,
+                             index: rhs, } => {
+                self.expr(lhs);
+                self.expr(rhs);
+            }
+            HirExpr::Call { callee: expr, args }
+            | HirExpr::MethodCall { receiver: expr,
+                                  args,
+                                  .. } => {
+                self.expr(expr);
+                for arg in args {
+                    self.expr(&arg.value);
+                }
+            }
+            HirExpr::If { branches,
+                          else_branch, } => {
+                for (cond, block) in branches {
+                    self.expr(cond);
+                    self.block(block);
+                }
+                if let Some(block) = else_branch {
+                    self.block(block);
+                }
+            }
+            HirExpr::Match { scrutinee,
+                             arms,
+                             span, } => {
+                self.expr(scrutinee);
+                for arm in arms {
+                    self.scope(|c| {
+                            c.pat(&arm.pat);
+                            c.expr(&arm.expr)
+                        })
+                }
+                self.check_match(scrutinee, arms, *span);
+            }
+            HirExpr::Fn(decl) => self.fn_decl(decl),
+            HirExpr::Break(expr) | HirExpr::Return(expr) => {
+                if let Some(expr) = expr {
+                    self.expr(expr);
+                }
+            }
+        }
+    }
+
+    fn check_match(&mut self,
+                   scrutinee: &HirExpr<'db>,
+                   arms: &'a [HirMatchArm<'db>],
+                   span: Span)
+    {
+        // a missing pattern has been reported already
+        let catch_all = |pat: &HirPat| {
+            matches!(pat,
+                     HirPat::Missing | HirPat::Wildcard | HirPat::Bind(_))
+        };
+        if arms.iter().any(|it| catch_all(&it.pat)) {
+            return
+        }
+
+        let named = arms.iter()
+                        .filter_map(|it| match &it.pat {
+                            HirPat::Path(path) if path.len() == 2 => {
+                                Some((&*path[0].name, &*path[1].name))
+                            }
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>();
+        let from_arms =
+            || named.iter().find_map(|(name, _)| self.lookup_enum(name));
+        let enum_decl = match self.enum_of(scrutinee).or_else(from_arms) {
+            Some(enum_decl) => enum_decl,
+            None => return,
+        };
+
+        let enum_name = &*enum_decl.name.name;
+        let missing = enum_decl.variants
+                               .iter()
+                               .map(|it| &*it.name.name)
+                               .filter(|&it| !named.contains(&(enum_name, it)))
+                               .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            self.diagnostics
+                .push(HirDiagnostic::NonExhaustiveMatch { span,
+                                                          enum_name,
+                                                          missing });
+        }
+    }
+
+    fn check_deprecated(&mut self, name: &'a HirName<'db>)
+    {
+        let deprecated = match self.lookup(name) {
+            Some(Binding { deprecated: Some(it),
+                           .. }) => it,
+            _ => return,
+        };
+        // `@deprecated('use foo')` says what to do instead.
+        let note = deprecated.args.iter().find_map(|it| match &it.value {
+                                             HirExpr::Lit(HirLit::Str(s)) => {
+                                                 Some(s.clone())
+                                             }
+                                             _ => None,
+                                         });
+        self.diagnostics
+            .push(HirDiagnostic::Deprecated { span: name.span,
+                                              name: &name.name,
+                                              note });
+    }
+
+    fn lookup(&self, name: &str) -> Option<Binding<'a, 'db>>
+    {
+        self.scopes
+            .iter()
+            .rev()
+            .flat_map(|it| it.iter().rev())
+            .find(|it| it.name == name)
+            .copied()
+    }
+
+    fn lookup_enum(&self, name: &str) -> Option<&'a HirEnumDecl<'db>>
+    {
+        self.lookup(name)?.enum_decl
+    }
+
+    /// The enum `expr` is a variant of, when it is `Os::Linux` or a name a
+    /// `let` bound to it.
+    fn enum_of(&self, expr: &HirExpr<'db>) -> Option<&'a HirEnumDecl<'db>>
+    {
+        match expr {
+            HirExpr::Path(path) if path.len() == 2 => {
+                let enum_decl = self.lookup_enum(&path[0])?;
+                enum_decl.variants
+                         .iter()
+                         .any(|it| it.name == path[1])
+                         .then_some(enum_decl)
+            }
+            HirExpr::Name(name) => self.lookup(name)?.variant_of,
+            _ => None,
+        }
+    }
+}
@@ -1,5 +1,13 @@
+use std::borrow::Cow;
 use std::ops::Deref;
 
+mod captures;
+pub mod check;
+pub mod lower;
+
+#[cfg(test)]
+mod tests;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct BytePos(pub u32);
 
@@ -29,10 +37,12 @@ macro_rules! id_ty {
 
 id_ty!(FileId);
 
+/// A name, in its NFC form. It borrows from the source text unless the
+/// source spells it another way.
 #[derive(Clone, Debug)]
 pub struct HirName<'db>
 {
-    pub name: &'db str,
+    pub name: Cow<'db, str>,
     pub span: Span,
 }
 
@@ -40,12 +50,12 @@ impl<'db> Deref for HirName<'db>
 {
     type Target = str;
 
-    fn deref(&self) -> &Self::Target { self.name }
+    fn deref(&self) -> &Self::Target { &self.name }
 }
 
 impl<'db> PartialEq for HirName<'db>
 {
-    fn eq(&self, other: &Self) -> bool { self.name.eq(other.name) }
+    fn eq(&self, other: &Self) -> bool { self.name.eq(&other.name) }
 }
 
 impl<'db> PartialEq<String> for HirName<'db>
@@ -53,47 +63,61 @@ impl<'db> PartialEq<String> for HirName<'db>
     fn eq(&self, other: &String) -> bool { self.name.eq(other) }
 }
 
+#[derive(Debug)]
 pub struct HirStructDecl<'db>
 {
+    pub attrs:  Vec<HirAttr<'db>>,
     pub name:   HirName<'db>,
     pub fields: Vec<HirStructField<'db>>,
 }
 
+#[derive(Debug)]
 pub struct HirStructFieldList<'db>
 {
     pub fields: Vec<HirStructField<'db>>,
 }
 
+#[derive(Debug)]
 pub struct HirStructField<'db>
 {
     pub name: HirName<'db>,
     pub ty:   HirTypeRef<'db>,
 }
 
+#[derive(Debug)]
 pub struct HirEnumDecl<'db>
 {
+    pub attrs:    Vec<HirAttr<'db>>,
     pub name:     HirName<'db>,
     pub variants: Vec<HirEnumVariant<'db>>,
 }
 
+#[derive(Debug)]
 pub struct HirEnumVariant<'db>
 {
     pub name: HirName<'db>,
 }
 
+#[derive(Debug)]
 pub struct HirFnDecl<'db>
 {
-    pub name: HirName<'db>,
-    pub args: Vec<HirFnArg<'db>>,
-    pub ret:  HirTypeRef<'db>,
+    pub attrs: Vec<HirAttr<'db>>,
+    pub name:  HirName<'db>,
+    pub args:  Vec<HirFnArg<'db>>,
+    pub ret:   Option<HirTypeRef<'db>>,
+    pub body:  HirBlock<'db>,
 }
 
+#[derive(Debug)]
 pub struct HirFnArg<'db>
 {
-    pub name: HirName<'db>,
-    pub ty:   HirTypeRef<'db>,
+    pub name:    HirName<'db>,
+    pub ty:      Option<HirTypeRef<'db>>,
+    /// The value of the argument when the caller doesn't pass it.
+    pub default: Option<HirExpr<'db>>,
 }
 
+#[derive(Debug)]
 pub enum HirDecl<'db>
 {
     Struct(HirStructDecl<'db>),
@@ -101,7 +125,191 @@ pub enum HirDecl<'db>
     Fn(HirFnDecl<'db>),
 }
 
-pub struct HirTypeRef<'db>
+#[derive(Debug)]
+pub enum HirTypeRef<'db>
+{
+    /// `!`
+    Never,
+    /// `_`
+    Placeholder,
+    Path
+    {
+        segments: Vec<HirName<'db>>,
+        ty_args:  Vec<HirTypeRef<'db>>,
+    },
+}
+
+#[derive(Debug)]
+pub struct HirBlock<'db>
+{
+    pub stmts: Vec<HirStmt<'db>>,
+}
+
+#[derive(Debug)]
+pub enum HirStmt<'db>
+{
+    /// The attributes of a `fn` go onto its declaration instead.
+    Expr
+    {
+        attrs: Vec<HirAttr<'db>>,
+        expr:  HirExpr<'db>,
+    },
+    Decl(HirDecl<'db>),
+    Let
+    {
+        attrs: Vec<HirAttr<'db>>,
+        name:  HirName<'db>,
+        ty:    Option<HirTypeRef<'db>>,
+        value: HirExpr<'db>,
+    },
+    While
+    {
+        attrs: Vec<HirAttr<'db>>,
+        cond:  HirExpr<'db>,
+        body:  HirBlock<'db>,
+    },
+    For
+    {
+        attrs:    Vec<HirAttr<'db>>,
+        pat:      HirPat<'db>,
+        iterable: HirExpr<'db>,
+        body:     HirBlock<'db>,
+    },
+}
+
+#[derive(Debug)]
+pub enum HirPat<'db>
+{
+    /// Stands in for a pattern that is missing from the source.
+    Missing,
+    /// `_`
+    Wildcard,
+    Bind(HirName<'db>),
+    Tuple(Vec<HirPat<'db>>),
+    Lit(HirLit<'db>),
+    /// An enum variant, `Os::Linux`.
+    Path(Vec<HirName<'db>>),
+}
+
+#[derive(Debug)]
+pub enum HirExpr<'db>
+{
+    /// Stands in for an expression that is missing from the source.
+    Missing,
+    Lit(HirLit<'db>),
+    Name(HirName<'db>),
+    /// An enum variant, `Os::Linux`.
+    Path(Vec<HirName<'db>>),
+    /// The text pieces and the embedded expressions of an interpolated
+    /// string, in order.
+    InterpolatedStr(Vec<HirExpr<'db>>),
+    Tuple(Vec<HirExpr<'db>>),
+    Array(Vec<HirExpr<'db>>),
+    /// The keys and the values of a `%{ key: value }` literal, in order.
+    Map(Vec<(HirExpr<'db>, HirExpr<'db>)>),
+    Block(HirBlock<'db>),
+    Prefix
+    {
+        op:   &'db str,
+        expr: Box<HirExpr<'db>>,
+    },
+    /// Ranges, `a..b` and `a..=b`, are binary operators too.
+    Binary
+    {
+        op:  &'db str,
+        lhs: Box<HirExpr<'db>>,
+        rhs: Box<HirExpr<'db>>,
+    },
+    Call
+    {
+        callee: Box<HirExpr<'db>>,
+        args:   Vec<HirCallArg<'db>>,
+    },
+    MethodCall
+    {
+        receiver: Box<HirExpr<'db>>,
+        method:   HirName<'db>,
+        args:     Vec<HirCallArg<'db>>,
+    },
+    Index
+    {
+        base:  Box<HirExpr<'db>>,
+        index: Box<HirExpr<'db>>,
+    },
+    Member
+    {
+        base:   Box<HirExpr<'db>>,
+        member: HirName<'db>,
+    },
+    If
+    {
+        branches:    Vec<(HirExpr<'db>, HirBlock<'db>)>,
+        else_branch: Option<HirBlock<'db>>,
+    },
+    Match
+    {
+        scrutinee: Box<HirExpr<'db>>,
+        arms:      Vec<HirMatchArm<'db>>,
+        /// The whole `match`, where problems with it are reported.
+        span:      Span,
+    },
+    Fn(Box<HirFnDecl<'db>>),
+    /// `|params| body`
+    Lambda
+    {
+        params:   Vec<HirFnArg<'db>>,
+        body:     Box<HirExpr<'db>>,
+        /// The names from around the lambda its body uses, each at its first
+        /// use.
+        captures: Vec<HirName<'db>>,
+    },
+    Break(Option<Box<HirExpr<'db>>>),
+    Continue,
+    Return(Option<Box<HirExpr<'db>>>),
+}
+
+#[derive(Debug)]
+pub enum HirLit<'db>
+{
+    /// The decoded value of a string.
+    Str(String),
+    /// The source text of a number, which isn't parsed until evaluation.
+    Number(&'db str),
+    /// The decoded value of a char.
+    Char(char),
+    Bool(bool),
+}
+
+/// `@name(args)`, on the declaration after it. Only declarations and `let`
+/// keep their attributes, the other statements lose them when lowered.
+#[derive(Debug)]
+pub struct HirAttr<'db>
+{
+    pub name: HirName<'db>,
+    pub args: Vec<HirCallArg<'db>>,
+}
+
+impl<'db> HirAttr<'db>
+{
+    /// The attribute called `name` among `attrs`, as in `@platform`.
+    pub fn find<'a>(attrs: &'a [Self], name: &str) -> Option<&'a Self>
+    {
+        attrs.iter().find(|it| *it.name == *name)
+    }
+}
+
+/// `pat => expr`
+#[derive(Debug)]
+pub struct HirMatchArm<'db>
+{
+    pub pat:  HirPat<'db>,
+    pub expr: HirExpr<'db>,
+}
+
+/// An argument of a call, `name = value` for the named ones.
+#[derive(Debug)]
+pub struct HirCallArg<'db>
 {
-    pub ty: HirName<'db>,
+    pub name:  Option<HirName<'db>>,
+    pub value: HirExpr<'db>,
 }
@@ -0,0 +1,572 @@
+//! Lowering of the syntax tree into the HIR.
+//!
+//! Names and operators borrow from the source text, so the lowered items live
+//! as long as it does. Names are normalized to NFC on the way.
+
+use std::ops::Range;
+
+use lfr_syntax::ast::{
+    self,
+    AstNode,
+    InterpolatedStrPart,
+};
+use lfr_syntax::rowan::TextRange;
+use lfr_syntax::{
+    SyntaxKind,
+    SyntaxToken,
+    ident,
+};
+
+use crate::captures::captures;
+use crate::{
+    BytePos,
+    HirAttr,
+    HirBlock,
+    HirCallArg,
+    HirDecl,
+    HirEnumDecl,
+    HirEnumVariant,
+    HirExpr,
+    HirFnArg,
+    HirFnDecl,
+    HirLit,
+    HirMatchArm,
+    HirName,
+    HirPat,
+    HirStmt,
+    HirStructDecl,
+    HirStructField,
+    HirTypeRef,
+    Span,
+};
+
+pub struct LowerCtx<'db>
+{
+    src: &'db str,
+}
+
+impl<'db> LowerCtx<'db>
+{
+    /// `src` is the text the lowered trees were parsed from.
+    pub fn new(src: &'db str) -> Self { Self { src } }
+
+    /// Lowers a function definition, or returns `None` when it has no name.
+    pub fn lower_fn_def(&self, fn_def: &ast::FnDef) -> Option<HirFnDecl<'db>>
+    {
+        let name = self.name(fn_def.ident_token()?);
+        let args = self.lower_params(fn_def.param_list());
+        let ret = fn_def.ret_type()
+                        .and_then(|it| it.type_ref())
+                        .and_then(|it| self.lower_type_ref(it));
+        let body = self.lower_block(fn_def.block());
+
+        // the attributes before a function are on the statement holding it.
+        Some(HirFnDecl { attrs: vec![],
+                         name,
+                         args,
+                         ret,
+                         body })
+    }
+
+    /// Lowers the parameters of a function or a lambda, leaving out the ones
+    /// without a name.
+    fn lower_params(&self, params: Option<ast::ParamList>)
+                    -> Vec<HirFnArg<'db>>
+    {
+        params.into_iter()
+              .flat_map(|it| it.params())
+              .filter_map(|param| {
+                  let name = self.name(param.ident_token()?);
+                  let ty =
+                      param.type_ref().and_then(|it| self.lower_type_ref(it));
+                  let default = param.default().map(|it| self.lower_expr(&it));
+                  Some(HirFnArg { name, ty, default })
+              })
+              .collect()
+    }
+
+    /// Lowers a struct definition, or returns `None` when it has no name.
+    /// Fields without a name or a type are left out.
+    pub fn lower_struct_def(&self,
+                            struct_def: &ast::StructDef)
+                            -> Option<HirStructDecl<'db>>
+    {
+        let name = self.name(struct_def.ident_token()?);
+        let fields = struct_def.field_list()
+                               .into_iter()
+                               .flat_map(|it| it.field_defs())
+                               .filter_map(|field| {
+                                   let name = self.name(field.ident_token()?);
+                                   let ty =
+                                       self.lower_type_ref(field.type_ref()?)?;
+                                   Some(HirStructField { name, ty })
+                               })
+                               .collect();
+
+        Some(HirStructDecl { attrs: self.lower_attrs(struct_def),
+                             name,
+                             fields })
+    }
+
+    /// Lowers an enum definition, or returns `None` when it has no name.
+    pub fn lower_enum_def(&self,
+                          enum_def: &ast::EnumDef)
+                          -> Option<HirEnumDecl<'db>>
+    {
+        let name = self.name(enum_def.ident_token()?);
+        let variants = enum_def.variant_list()
+                               .into_iter()
+                               .flat_map(|it| it.variants())
+                               .filter_map(|variant| {
+                                   let name = self.name(variant.ident_token()?);
+                                   Some(HirEnumVariant { name })
+                               })
+                               .collect();
+
+        Some(HirEnumDecl { attrs: self.lower_attrs(enum_def),
+                           name,
+                           variants })
+    }
+
+    /// Lowers the attributes of `owner`, leaving out the ones without a name.
+    fn lower_attrs(&self, owner: &impl ast::HasAttrs) -> Vec<HirAttr<'db>>
+    {
+        owner.attrs()
+             .filter_map(|attr| {
+                 let name = self.name(attr.ident_token()?);
+                 let args = self.lower_call_args(attr.fn_call_args());
+                 Some(HirAttr { name, args })
+             })
+             .collect()
+    }
+
+    pub fn lower_type_ref(&self,
+                          type_ref: ast::TypeRef)
+                          -> Option<HirTypeRef<'db>>
+    {
+        if type_ref.bang_token().is_some() {
+            return Some(HirTypeRef::Never)
+        }
+        if type_ref.underscore_token().is_some() {
+            return Some(HirTypeRef::Placeholder)
+        }
+
+        let segments = self.lower_path(&type_ref.path()?);
+        let ty_args = type_ref.generic_arg_list()
+                              .into_iter()
+                              .flat_map(|it| it.type_refs())
+                              .filter_map(|it| self.lower_type_ref(it))
+                              .collect();
+        Some(HirTypeRef::Path { segments, ty_args })
+    }
+
+    /// The names in `path`, without the leading `::`.
+    fn lower_path(&self, path: &ast::Path) -> Vec<HirName<'db>>
+    {
+        path.ident_token()
+            .into_iter()
+            .chain(path.path_frags().filter_map(|it| it.ident_token()))
+            .map(|it| self.name(it))
+            .collect()
+    }
+
+    pub fn lower_block(&self, block: Option<ast::Block>) -> HirBlock<'db>
+    {
+        let stmts =
+            block.map_or_else(Vec::new, |it| self.lower_stmts(it.stmts()));
+        HirBlock { stmts }
+    }
+
+    /// Lowers `stmts`, those of a `Root` or of a `Block`.
+    pub fn lower_stmts(&self,
+                       stmts: impl Iterator<Item = ast::Stmt>)
+                       -> Vec<HirStmt<'db>>
+    {
+        stmts.filter_map(|it| self.lower_stmt(&it)).collect()
+    }
+
+    fn lower_stmt(&self, stmt: &ast::Stmt) -> Option<HirStmt<'db>>
+    {
+        let inner = match stmt.inner() {
+            Some(inner) => inner,
+            None => {
+                let expr = self.lower_expr(&stmt.expr()?);
+                return Some(HirStmt::Expr { attrs: vec![],
+                                            expr })
+            }
+        };
+
+        let stmt = match inner {
+            ast::StmtInner::ExprStmt(it) => {
+                let mut attrs = self.lower_attrs(&it);
+                let mut expr = self.lower_opt_expr(it.expr());
+                if let HirExpr::Fn(fn_decl) = &mut expr {
+                    fn_decl.attrs = std::mem::take(&mut attrs);
+                }
+                HirStmt::Expr { attrs, expr }
+            }
+            ast::StmtInner::DeclarationStmt(it) => {
+                let ty = it.type_ref().and_then(|it| self.lower_type_ref(it));
+                HirStmt::Let { attrs: self.lower_attrs(&it),
+                               name: self.name(it.ident_token()?),
+                               ty,
+                               value: self.lower_opt_expr(it.expr()) }
+            }
+            ast::StmtInner::WhileStmt(it) => {
+                HirStmt::While { attrs: self.lower_attrs(&it),
+                                 cond:  self.lower_opt_expr(it.expr()),
+                                 body:  self.lower_block(it.block()), }
+            }
+            ast::StmtInner::ForStmt(it) => {
+                HirStmt::For { attrs:    self.lower_attrs(&it),
+                               pat:      self.lower_opt_pat(it.pat()),
+                               iterable: self.lower_opt_expr(it.expr()),
+                               body:     self.lower_block(it.block()), }
+            }
+            ast::StmtInner::StructDef(it) => {
+                HirStmt::Decl(HirDecl::Struct(self.lower_struct_def(&it)?))
+            }
+            ast::StmtInner::EnumDef(it) => {
+                HirStmt::Decl(HirDecl::Enum(self.lower_enum_def(&it)?))
+            }
+        };
+        Some(stmt)
+    }
+
+    pub fn lower_expr(&self, expr: &ast::Expr) -> HirExpr<'db>
+    {
+        match expr {
+            ast::Expr::PrimaryExpr(it) => self.lower_primary_expr(it),
+            ast::Expr::BinExpr(it) => {
+                let op = match it.op_token() {
+                    Some(op) => self.text(&op),
+                    None => return HirExpr::Missing,
+                };
+                let lhs = self.lower_boxed_expr(it.lhs());
+                let rhs = self.lower_boxed_expr(it.rhs());
+                HirExpr::Binary { op, lhs, rhs }
+            }
+            ast::Expr::RangeExpr(it) => {
+                let op = match it.op_token() {
+                    Some(op) => self.text(&op),
+                    None => return HirExpr::Missing,
+                };
+                let lhs = self.lower_boxed_expr(it.start());
+                let rhs = self.lower_boxed_expr(it.end());
+                HirExpr::Binary { op, lhs, rhs }
+            }
+            ast::Expr::PipeExpr(it) => self.lower_pipe_expr(it),
+            // `cond ? a : b` is `if cond { a } else { b }`
+            ast::Expr::TernaryExpr(it) => {
+                let block = |expr| {
+                    let expr = self.lower_opt_expr(expr);
+                    let stmts = vec![HirStmt::Expr { attrs: vec![],
+                                                     expr }];
+                    HirBlock { stmts }
+                };
+                let cond = self.lower_opt_expr(it.cond());
+                let then_block = block(it.then_expr());
+                HirExpr::If { branches:    vec![(cond, then_block)],
+                              else_branch: Some(block(it.else_expr())), }
+            }
+            ast::Expr::PrefixUnaryExpr(it) => {
+                let op = match it.op_token() {
+                    Some(op) => self.text(&op),
+                    None => return HirExpr::Missing,
+                };
+                let expr = self.lower_boxed_expr(it.expr());
+                HirExpr::Prefix { op, expr }
+            }
+            ast::Expr::FnCallExpr(it) => {
+                let callee = self.lower_boxed_expr(it.expr());
+                let args = self.lower_call_args(it.fn_call_args());
+                HirExpr::Call { callee, args }
+            }
+            ast::Expr::IndexExpr(it) => {
+                let base = self.lower_boxed_expr(it.expr());
+                let index = it.index_expr_brackets().and_then(|it| it.expr());
+                let index = self.lower_boxed_expr(index);
+                HirExpr::Index { base, index }
+            }
+            ast::Expr::MemberAccessExpr(it) => {
+                let member = match it.ident_token() {
+                    Some(member) => self.name(member),
+                    None => return HirExpr::Missing,
+                };
+                let base = self.lower_boxed_expr(it.expr());
+                HirExpr::Member { base, member }
+            }
+            ast::Expr::MethodCallExpr(it) => {
+                let method = match it.ident_token() {
+                    Some(method) => self.name(method),
+                    None => return HirExpr::Missing,
+                };
+                let receiver = self.lower_boxed_expr(it.expr());
+                let args = self.lower_call_args(it.fn_call_args());
+                HirExpr::MethodCall { receiver,
+                                      method,
+                                      args }
+            }
+            ast::Expr::FnDef(it) => match self.lower_fn_def(it) {
+                Some(fn_decl) => HirExpr::Fn(Box::new(fn_decl)),
+                None => HirExpr::Missing,
+            },
+            ast::Expr::LambdaExpr(it) => {
+                let params = self.lower_params(it.param_list());
+                let body = self.lower_boxed_expr(it.body());
+                let captures = captures(&params, &body);
+                HirExpr::Lambda { params,
+                                  body,
+                                  captures }
+            }
+        }
+    }
+
+    /// Lowers `x |> f(a)` into `f(x, a)`, `x |> y.f(a)` into `y.f(x, a)`
+    /// and `x |> f` into `f(x)`.
+    fn lower_pipe_expr(&self, pipe: &ast::PipeExpr) -> HirExpr<'db>
+    {
+        let arg = HirCallArg { name:  None,
+                               value: self.lower_opt_expr(pipe.lhs()), };
+        match self.lower_opt_expr(pipe.rhs()) {
+            HirExpr::Call { callee, mut args } => {
+                args.insert(0, arg);
+                HirExpr::Call { callee, args }
+            }
+            HirExpr::MethodCall { receiver,
+                                  method,
+                                  mut args, } => {
+                args.insert(0, arg);
+                HirExpr::MethodCall { receiver,
+                                      method,
+                                      args }
+            }
+            callee => HirExpr::Call { callee: Box::new(callee),
+                                      args:   vec![arg], },
+        }
+    }
+
+    fn lower_primary_expr(&self, expr: &ast::PrimaryExpr) -> HirExpr<'db>
+    {
+        let inner = match expr.inner() {
+            Some(inner) => inner,
+            // `this` is a name like any other here.
+            None => {
+                return expr.ident_token()
+                           .or_else(|| expr.this_token())
+                           .map_or(HirExpr::Missing, |it| {
+                               HirExpr::Name(self.name(it))
+                           })
+            }
+        };
+
+        match inner {
+            ast::PrimaryExprInner::TupleExpr(it) => {
+                HirExpr::Tuple(self.lower_exprs(it.exprs()))
+            }
+            ast::PrimaryExprInner::ArrExpr(it) => {
+                HirExpr::Array(self.lower_exprs(it.exprs()))
+            }
+            ast::PrimaryExprInner::MapExpr(it) => {
+                let entries = it.map_entrys()
+                                .map(|it| {
+                                    (self.lower_opt_expr(it.key()),
+                                     self.lower_opt_expr(it.value()))
+                                })
+                                .collect();
+                HirExpr::Map(entries)
+            }
+            ast::PrimaryExprInner::Block(it) => {
+                HirExpr::Block(self.lower_block(Some(it)))
+            }
+            ast::PrimaryExprInner::Path(it) => {
+                HirExpr::Path(self.lower_path(&it))
+            }
+            ast::PrimaryExprInner::LitVal(it) => match self.lower_lit(&it) {
+                Some(lit) => HirExpr::Lit(lit),
+                None => HirExpr::Missing,
+            },
+            ast::PrimaryExprInner::InterpolatedStr(it) => match it.parts() {
+                Ok(parts) => {
+                    let parts = parts.into_iter()
+                                     .map(|part| match part {
+                                         InterpolatedStrPart::Str(s) => {
+                                             HirExpr::Lit(HirLit::Str(s))
+                                         }
+                                         InterpolatedStrPart::Expr(e) => {
+                                             self.lower_expr(&e)
+                                         }
+                                     })
+                                     .collect();
+                    HirExpr::InterpolatedStr(parts)
+                }
+                // invalid escapes are reported by the parser
+                Err(_) => HirExpr::Missing,
+            },
+            ast::PrimaryExprInner::IfExpr(it) => {
+                let branches =
+                    it.if_branch()
+                      .map(|it| (it.expr(), it.block()))
+                      .into_iter()
+                      .chain(it.else_if_branchs()
+                               .map(|it| (it.expr(), it.block())))
+                      .map(|(cond, block)| {
+                          (self.lower_opt_expr(cond), self.lower_block(block))
+                      })
+                      .collect();
+                let else_branch =
+                    it.else_branch().map(|it| self.lower_block(it.block()));
+                HirExpr::If { branches,
+                              else_branch }
+            }
+            ast::PrimaryExprInner::MatchExpr(it) => {
+                let arms =
+                    it.match_arm_list()
+                      .into_iter()
+                      .flat_map(|it| it.match_arms())
+                      .map(|arm| {
+                          HirMatchArm { pat:  self.lower_opt_pat(arm.pat()),
+                                        expr: self.lower_opt_expr(arm.expr()), }
+                      })
+                      .collect();
+                HirExpr::Match { scrutinee: self.lower_boxed_expr(it.expr()),
+                                 arms,
+                                 span: span(it.syntax().text_range()) }
+            }
+            ast::PrimaryExprInner::BreakStmt(it) => {
+                HirExpr::Break(it.expr()
+                                 .map(|it| Box::new(self.lower_expr(&it))))
+            }
+            ast::PrimaryExprInner::ContinueStmt(_) => HirExpr::Continue,
+            ast::PrimaryExprInner::ReturnStmt(it) => {
+                HirExpr::Return(it.expr()
+                                  .map(|it| Box::new(self.lower_expr(&it))))
+            }
+        }
+    }
+
+    pub fn lower_pat(&self, pat: &ast::Pat) -> HirPat<'db>
+    {
+        match pat {
+            ast::Pat::IdentPat(it) => match it.ident_token() {
+                Some(name) => HirPat::Bind(self.name(name)),
+                None => HirPat::Missing,
+            },
+            ast::Pat::TuplePat(it) => {
+                HirPat::Tuple(it.pats().map(|it| self.lower_pat(&it)).collect())
+            }
+            ast::Pat::WildcardPat(_) => HirPat::Wildcard,
+            ast::Pat::LitPat(it) => {
+                match it.lit_val().and_then(|it| self.lower_lit(&it)) {
+                    Some(lit) => HirPat::Lit(lit),
+                    None => HirPat::Missing,
+                }
+            }
+            ast::Pat::PathPat(it) => match it.path() {
+                Some(path) => HirPat::Path(self.lower_path(&path)),
+                None => HirPat::Missing,
+            },
+        }
+    }
+
+    fn lower_opt_pat(&self, pat: Option<ast::Pat>) -> HirPat<'db>
+    {
+        pat.map_or(HirPat::Missing, |it| {
+               self.lower_pat(&it)
+           })
+    }
+
+    fn lower_lit(&self, lit: &ast::LitVal) -> Option<HirLit<'db>>
+    {
+        let lit = match lit.inner()? {
+            // invalid escapes are reported by the parser
+            ast::LitValInner::StringLit(it) => HirLit::Str(it.value().ok()?),
+            ast::LitValInner::CharLit(it) => HirLit::Char(it.value().ok()?),
+            ast::LitValInner::NumberLit(it) => {
+                let number = it.int_number_token()
+                               .or_else(|| it.float_number_token())?;
+                HirLit::Number(self.text(&number))
+            }
+            ast::LitValInner::BooleanLit(it) => {
+                HirLit::Bool(it.true_token().is_some())
+            }
+        };
+        Some(lit)
+    }
+
+    /// Lowers the arguments of a call, `name = value` being a named argument
+    /// rather than an assignment.
+    fn lower_call_args(&self,
+                       args: Option<ast::FnCallArgs>)
+                       -> Vec<HirCallArg<'db>>
+    {
+        args.into_iter()
+            .flat_map(|it| it.exprs())
+            .map(|arg| match self.named_arg(&arg) {
+                Some((name, value)) => {
+                    HirCallArg { name:  Some(self.name(name)),
+                                 value: self.lower_opt_expr(value), }
+                }
+                None => HirCallArg { name:  None,
+                                     value: self.lower_expr(&arg), },
+            })
+            .collect()
+    }
+
+    /// Splits `name = value` into its name and value.
+    fn named_arg(&self,
+                 arg: &ast::Expr)
+                 -> Option<(SyntaxToken, Option<ast::Expr>)>
+    {
+        let bin = match arg {
+            ast::Expr::BinExpr(bin) => bin,
+            _ => return None,
+        };
+        if bin.op_token()?.kind() != SyntaxKind::EQ {
+            return None
+        }
+        let name = match bin.lhs()? {
+            ast::Expr::PrimaryExpr(it) if it.inner().is_none() => {
+                it.ident_token()?
+            }
+            _ => return None,
+        };
+        Some((name, bin.rhs()))
+    }
+
+    fn lower_exprs(&self,
+                   exprs: impl Iterator<Item = ast::Expr>)
+                   -> Vec<HirExpr<'db>>
+    {
+        exprs.map(|it| self.lower_expr(&it)).collect()
+    }
+
+    fn lower_opt_expr(&self, expr: Option<ast::Expr>) -> HirExpr<'db>
+    {
+        expr.map_or(HirExpr::Missing, |it| {
+                self.lower_expr(&it)
+            })
+    }
+
+    fn lower_boxed_expr(&self, expr: Option<ast::Expr>) -> Box<HirExpr<'db>>
+    {
+        Box::new(self.lower_opt_expr(expr))
+    }
+
+    fn name(&self, token: SyntaxToken) -> HirName<'db>
+    {
+        HirName { name: ident::normalize(self.text(&token)),
+                  span: span(token.text_range()), }
+    }
+
+    fn text(&self, token: &SyntaxToken) -> &'db str
+    {
+        &self.src[Range::<usize>::from(token.text_range())]
+    }
+}
+
+fn span(range: TextRange) -> Span
+{
+    Span { lo: BytePos(range.start().into()),
+           hi: BytePos(range.end().into()), }
+}
@@ -0,0 +1,309 @@
+//! Lowers snippets of source and checks what comes out of it.
+
+use lfr_parser::parser::{
+    parse,
+    parse_expr_fragment,
+};
+use lfr_syntax::SyntaxNode;
+use lfr_syntax::ast::{
+    self,
+    AstNode,
+};
+
+use crate::check::check_stmts;
+use crate::lower::LowerCtx;
+use crate::{
+    HirCallArg,
+    HirDecl,
+    HirExpr,
+    HirLit,
+    HirStmt,
+};
+
+/// The names the first lambda in `text` captures.
+fn lambda_captures(text: &str) -> Vec<String>
+{
+    let (green, errors) = parse(text);
+    assert!(errors.is_empty(), "{:?}", errors);
+    let lambda = SyntaxNode::new_root(green).descendants()
+                                            .find_map(ast::LambdaExpr::cast)
+                                            .expect("no lambda");
+
+    match LowerCtx::new(text).lower_expr(&lambda.into()) {
+        HirExpr::Lambda { captures, .. } => {
+            captures.into_iter()
+                    .map(|it| it.name.into_owned())
+                    .collect()
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn lambda_captures_free_names()
+{
+    let cases: &[(&str, &[&str])] =
+        &[("sources.filter(|f| f.ends_with(ext))", &["ext"]),
+          ("|a, b| a + b", &[]),
+          ("|| cc(src, flags = flags, src)", &["cc", "src", "flags"]),
+          ("|x| { let y = x\n y + z }", &["z"]),
+          ("|| { y = 1\n let y = 2\n y }", &["y"]),
+          ("|xs| { for (k, v) in xs { f(k, v, w) }\n }", &["f", "w"]),
+          ("|| { fn go(n) { go(n - 1) + m }\n go(1) }", &["m"]),
+          ("|a| |b| a + b + c", &["c"]),
+          ("|a| %{ a: |a| a + k }", &["k"]),
+          ("|i| i < n ? a : i..=n", &["n", "a"]),
+          ("|a| if a { b } else if c { a } else { d }", &["b", "c", "d"]),
+          ("|| match x { (a, Os::Linux) => a + b, a => a }", &["x", "b"]),
+          ("|os| os == Os::Linux", &["Os"]),
+          ("|| this.srcs", &["this"]),
+          // the same name, with `é` composed and decomposed
+          ("|| { let caf\u{e9} = 1\n cafe\u{301} }", &[]),
+          ("|| cafe\u{301} + caf\u{e9}", &["caf\u{e9}"])];
+
+    for (text, expected) in cases {
+        assert_eq!(lambda_captures(text),
+                   *expected,
+                   "{}",
+                   text);
+    }
+}
+
+/// The statements of `text`, which has to parse without errors.
+fn lower(text: &str) -> Vec<HirStmt<'_>>
+{
+    let (green, errors) = parse(text);
+    assert!(errors.is_empty(), "{:?}", errors);
+    let root = ast::Root::cast(SyntaxNode::new_root(green)).unwrap();
+    LowerCtx::new(text).lower_stmts(root.stmts())
+}
+
+/// The diagnostics for `text`, each with the text it points at.
+fn diagnostics(text: &str) -> Vec<String>
+{
+    check_stmts(&lower(text)).iter()
+                             .map(|it| {
+                                 let span = it.span();
+                                 let range =
+                                     span.lo.0 as usize..span.hi.0 as usize;
+                                 format!("{:?}: {}", &text[range], it)
+                             })
+                             .collect()
+}
+
+#[test]
+fn non_exhaustive_matches()
+{
+    let os = "enum Os { Linux, Windows, Mac }\n";
+    let cases: &[(&str, &[&str])] =
+        &[("match os { Os::Linux => 1, Os::Windows => 2, Os::Mac => 3 }", &[]),
+          ("match os { Os::Linux => 1, _ => 2 }", &[]),
+          ("match os { Os::Linux => 1, other => 2 }", &[]),
+          ("match os { 'linux' => 1, 'mac' => 2 }", &[]),
+          ("match os { Os::Linux => 1 }",
+           &["\"match os { Os::Linux => 1 }\": non-exhaustive match, \
+              missing `Os::Windows`, `Os::Mac`"]),
+          ("f(|| { match os { Os::Mac => 1, Os::Linux => 2 } })",
+           &["\"match os { Os::Mac => 1, Os::Linux => 2 }\": \
+              non-exhaustive match, missing `Os::Windows`"]),
+          // over a variant, the arms needn't name one
+          ("let os = Os::Linux\nmatch os { 'linux' => 1 }",
+           &["\"match os { 'linux' => 1 }\": non-exhaustive match, \
+              missing `Os::Linux`, `Os::Windows`, `Os::Mac`"]),
+          ("let os = Os::Mac\nmatch os { Os::Mac => 1, _ => 2 }", &[]),
+          ("match Os::Mac { Os::Mac => 1, Os::Linux => 2 }",
+           &["\"match Os::Mac { Os::Mac => 1, Os::Linux => 2 }\": \
+              non-exhaustive match, missing `Os::Windows`"]),
+          // not a variant of `Os`
+          ("match Os::Bsd { 'bsd' => 1 }", &[])];
+
+    for (text, expected) in cases {
+        let text = format!("{}{}\n", os, text);
+        assert_eq!(diagnostics(&text),
+                   *expected,
+                   "{}",
+                   text);
+    }
+    // without the declaration, nothing is known about `Os`
+    assert_eq!(diagnostics("match os { Os::Linux => 1 }\n"),
+               Vec::<String>::new());
+}
+
+#[test]
+fn deprecated_uses()
+{
+    let cases: &[(&str, &[&str])] =
+        &[("@deprecated('use compile')\nfn cc(src) {}\ncc('a.c')\n",
+           &["\"cc\": use of deprecated `cc`: use compile"]),
+          ("@deprecated\nlet app = 1\nf(app, app)\n",
+           &["\"app\": use of deprecated `app`",
+             "\"app\": use of deprecated `app`"]),
+          ("@platform('linux') @deprecated\nstruct Opts {}\nOpts()\n",
+           &["\"Opts\": use of deprecated `Opts`"]),
+          ("cc(1)\n@deprecated\nfn cc() {}\n",
+           &["\"cc\": use of deprecated `cc`"]),
+          ("@platform('linux')\nfn cc(src) {}\ncc(1)\n", &[]),
+          // shadowed by something that isn't deprecated
+          ("@deprecated\nfn cc() {}\nfn build(cc) { cc(1) }\n", &[]),
+          ("@deprecated\nfn cc() {}\nlet cc = 1\ncc\n", &[]),
+          ("@deprecated\nfn cc() {}\nsrcs.map(|cc| cc)\n", &[]),
+          ("@deprecated\nfn cc() {}\nmatch x { cc => cc }\n", &[]),
+          ("@deprecated\nenum Os { Linux }\nos = Os::Linux\n",
+           &["\"Os\": use of deprecated `Os`"])];
+
+    for (text, expected) in cases {
+        assert_eq!(diagnostics(text), *expected, "{}", text);
+    }
+}
+
+/// The arguments of a call as source text again.
+fn render_args(args: &[HirCallArg]) -> String
+{
+    args.iter()
+        .map(|it| render(&it.value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `expr` as source text again, for the few kinds of expressions the tests
+/// lower.
+fn render(expr: &HirExpr) -> String
+{
+    match expr {
+        HirExpr::Missing => "?".to_owned(),
+        HirExpr::Name(name) => name.to_string(),
+        HirExpr::Lit(HirLit::Str(s)) => format!("{:?}", s),
+        HirExpr::Lit(HirLit::Char(c)) => format!("c{:?}", c),
+        HirExpr::Call { callee, args: it } => {
+            format!("{}({})",
+                    render(callee),
+                    render_args(it))
+        }
+        HirExpr::MethodCall { receiver,
+                              method,
+                              args: it, } => {
+            format!("{}.{}({})",
+                    render(receiver),
+                    method.name,
+                    render_args(it))
+        }
+        other => panic!("can't render {:?}", other),
+    }
+}
+
+#[test]
+fn pipes_lower_to_calls()
+{
+    let cases = [("glob('*.c') |> exclude(bad) |> sort()",
+                  r#"sort(exclude(glob("*.c"), bad))"#),
+                 ("srcs |> sort", "sort(srcs)"),
+                 ("srcs\n    |> fs.filter(ok)", "fs.filter(srcs, ok)"),
+                 ("srcs |> ", "?(srcs)")];
+
+    for (text, expected) in cases {
+        let expr = match parse_expr_fragment(text) {
+            Ok((expr, _)) => expr,
+            Err(errors) => panic!("{}: {:?}", text, errors),
+        };
+        assert_eq!(render(&LowerCtx::new(text).lower_expr(&expr)),
+                   expected,
+                   "{}",
+                   text);
+    }
+}
+
+#[test]
+fn char_lits_decode()
+{
+    let text = concat!(r#"f(c'a', c'\r', c'\0', c'\"', c'\$', "#,
+                       r#"c'\'', c'\\', c'\u{1F600}')"#);
+    let expr = match parse_expr_fragment(text) {
+        Ok((expr, _)) => expr,
+        Err(errors) => panic!("{:?}", errors),
+    };
+    assert_eq!(render(&LowerCtx::new(text).lower_expr(&expr)),
+               "f(c'a', c'\\r', c'\\0', c'\"', c'$', c'\\'', c'\\\\', \
+                c'\u{1F600}')");
+}
+
+#[test]
+fn if_branches_lower()
+{
+    let text = "if a { 1 } else if b { 2 } else { 3 }";
+    let expr = match parse_expr_fragment(text) {
+        Ok((expr, _)) => expr,
+        Err(errors) => panic!("{:?}", errors),
+    };
+    match LowerCtx::new(text).lower_expr(&expr) {
+        HirExpr::If { branches,
+                      else_branch, } => {
+            let conds = branches.iter()
+                                .map(|(cond, _)| render(cond))
+                                .collect::<Vec<_>>();
+            assert_eq!(conds, ["a", "b"]);
+            assert!(branches.iter().all(|(_, block)| block.stmts.len() == 1));
+            assert_eq!(else_branch.map(|it| it.stmts.len()), Some(1));
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn control_stmts_lower()
+{
+    let stmts = lower("return 1\nbreak\ncontinue\n");
+    assert!(matches!(stmts.as_slice(),
+                     [HirStmt::Expr { expr: HirExpr::Return(Some(_)),
+                                      .. },
+                      HirStmt::Expr { expr: HirExpr::Break(None),
+                                      .. },
+                      HirStmt::Expr { expr: HirExpr::Continue,
+                                      .. }]));
+}
+
+#[test]
+fn attrs_lower_onto_stmts()
+{
+    let text = concat!("@deprecated('use compile')\nfn cc(src) {}\n",
+                       "@platform('linux') @doc('the app')\n",
+                       "let app = executable('app')\n",
+                       "@platform('windows')\nstruct Opts { static: bool }\n",
+                       "@doc('kinds')\nenum Kind { Lib, Exe }\n",
+                       "@unroll\nfor src in srcs {}\n",
+                       "@unroll while more() {}\n",
+                       "@platform('mac') app = executable('app')\n",
+                       "f(1)\n");
+
+    let attrs =
+        lower(text).iter()
+                   .map(|stmt| {
+                       let attrs = match stmt {
+                           HirStmt::Expr { expr: HirExpr::Fn(it),
+                                           .. } => &it.attrs,
+                           HirStmt::Expr { attrs, .. } => attrs,
+                           HirStmt::Let { attrs, .. } => attrs,
+                           HirStmt::Decl(HirDecl::Struct(it)) => &it.attrs,
+                           HirStmt::Decl(HirDecl::Enum(it)) => &it.attrs,
+                           HirStmt::While { attrs, .. } => attrs,
+                           HirStmt::For { attrs, .. } => attrs,
+                           HirStmt::Decl(HirDecl::Fn(it)) => &it.attrs,
+                       };
+                       attrs.iter()
+                            .map(|it| {
+                                format!("@{}({})",
+                                        it.name.name,
+                                        render_args(&it.args))
+                            })
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                   })
+                   .collect::<Vec<_>>();
+    assert_eq!(attrs, [r#"@deprecated("use compile")"#,
+                       r#"@platform("linux") @doc("the app")"#,
+                       r#"@platform("windows")"#,
+                       r#"@doc("kinds")"#,
+                       "@unroll()",
+                       "@unroll()",
+                       r#"@platform("mac")"#,
+                       ""]);
+}
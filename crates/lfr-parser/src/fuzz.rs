@@ -0,0 +1,122 @@
+//! The invariants the fuzz targets and the property tests hold the parser
+//! to.
+
+use lfr_syntax::SyntaxNode;
+use lfr_syntax::ast::AstNode;
+use lfr_syntax::rowan::{
+    TextRange,
+    TextSize,
+};
+
+use crate::parser::error::ParseError;
+use crate::parser::reparsing::{
+    TextEdit,
+    reparse,
+};
+use crate::parser::{
+    FragmentResult,
+    debug_ast,
+    parse,
+    parse_block_fragment,
+    parse_expr_fragment,
+    parse_stmt_fragment,
+    parse_type_fragment,
+};
+
+/// Parses `text`, as a file and as each kind of fragment, panicking if a
+/// tree doesn't give it back verbatim or if an error points past its end.
+/// The parser itself panics when it leaves a marker unfinished or stops
+/// making progress.
+pub fn check_parser(text: &str)
+{
+    let (green, errors) = parse(text);
+    check_tree(&SyntaxNode::new_root(green),
+               &errors,
+               text);
+
+    check_fragment(untyped(parse_expr_fragment(text)), text);
+    check_fragment(untyped(parse_stmt_fragment(text)), text);
+    check_fragment(untyped(parse_block_fragment(text)),
+                   text);
+    check_fragment(untyped(parse_type_fragment(text)), text);
+}
+
+fn untyped<N: AstNode>(result: FragmentResult<N>)
+                       -> FragmentResult<SyntaxNode>
+{
+    result.map(|(node, errors)| (node.syntax().clone(), errors))
+}
+
+fn check_fragment(result: FragmentResult<SyntaxNode>, text: &str)
+{
+    match result {
+        Ok((node, errors)) => {
+            let root = node.ancestors().last().unwrap();
+            check_tree(&root, &errors, text);
+        }
+        Err(errors) => check_errors(&errors, text),
+    }
+}
+
+fn check_tree(root: &SyntaxNode, errors: &[ParseError], text: &str)
+{
+    assert_eq!(root.text().to_string(),
+               text,
+               "the tree isn't lossless");
+    check_errors(errors, text);
+}
+
+fn check_errors(errors: &[ParseError], text: &str)
+{
+    let len = TextSize::of(text);
+    for err in errors {
+        let ranges = std::iter::once(err.range).chain(err.labels
+                                                         .iter()
+                                                         .map(|it| it.range));
+        for range in ranges {
+            assert!(range.end() <= len,
+                    "error `{}` at {:?} is past the end of the input",
+                    err,
+                    range);
+        }
+    }
+}
+
+/// Reparses `text` after `edit`, panicking unless that gives the tree and
+/// the errors a full parse of the edited text does.
+pub fn check_reparse(text: &str, edit: &TextEdit)
+{
+    let (green, errors) = parse(text);
+    let new_text = edit.apply(text);
+    let (reparsed, reparsed_errors) = reparse(&SyntaxNode::new_root(green),
+                                              &errors,
+                                              edit,
+                                              &new_text);
+    let (parsed, parsed_errors) = parse(&new_text);
+    assert_eq!(debug_ast(&SyntaxNode::new_root(reparsed)),
+               debug_ast(&SyntaxNode::new_root(parsed)),
+               "the reparsed tree differs");
+    assert_eq!(reparsed_errors, parsed_errors,
+               "the errors differ");
+}
+
+/// Reads the input of [`check_reparse`] out of fuzzer data: the start and
+/// the length of the deleted range on a line each, then the inserted text,
+/// with `\n` for newlines, on a third line, then the text itself.
+pub fn reparse_input(data: &str) -> Option<(String, TextEdit)>
+{
+    let mut lines = data.splitn(4, '\n');
+    let start = lines.next()?.parse::<u32>().ok()?;
+    let len = lines.next()?.parse::<u32>().ok()?;
+    let insert = lines.next()?.replace("\\n", "\n");
+    let text = lines.next()?;
+
+    let delete = TextRange::at(start.into(), len.into());
+    if delete.end() > TextSize::of(text)
+       || !text.is_char_boundary(delete.start().into())
+       || !text.is_char_boundary(delete.end().into())
+    {
+        return None
+    }
+    Some((text.to_owned(), TextEdit::new(delete, insert)))
+}
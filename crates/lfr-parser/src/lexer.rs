@@ -3,158 +3,196 @@ use std::ops::Range;
 
 use lfr_stdx::With;
 use lfr_syntax::span::Span;
-use lfr_syntax::syntax_kind::SyntaxKind;
-use lfr_syntax::T;
+use lfr_syntax::syntax_kind::SyntaxKind::{
+    self,
+    *,
+};
 use logos::Logos;
 
-#[derive(Logos, Copy, Clone, Debug, PartialEq, PartialOrd, Eq)]
-pub enum Tk
+mod generated;
+
+pub use generated::Tk;
+
+/// Called after the `r`, the `#`s and the opening quote of a raw string were
+/// matched; eats everything up to and including the same quote followed by
+/// as many `#`s.
+fn lex_raw_str(lex: &mut logos::Lexer<Tk>) -> bool
 {
-    #[regex(r#"[a-zA-Z_][a-zA-Z0-9_]*"#)]
-    Ident,
-    #[regex(r#"([1-9][0-9]*|0x[0-9a-fA-F]+|0b[01]+|0[0-7]+|0)[uU]?[lL]?"#)]
-    IntNumber,
-    #[regex(r#"'(\\['nt\\]|[^'\\])+'"#)]
-    Str,
-    #[regex(r#"'''([^']*|'[^']|''[^'])*'''"#)]
-    MultilineStr,
-    #[regex(r#"//[^\n]*"#)]
-    Comment,
-    #[regex(r#"/\*([^*]|\**[^*/])*\*+/"#)]
-    BlockComment,
-    #[regex(r#"[ \t\r]+"#)]
-    Whitespace,
-    #[token("+")]
-    Plus,
-    #[token("-")]
-    Minus,
-    #[token("*")]
-    Asterisk,
-    #[token("/")]
-    Slash,
-    #[token("%")]
-    Percent,
-    #[token(">")]
-    RAngle,
-    #[token("<")]
-    LAngle,
-    #[token("=")]
-    Eq,
-    #[token("!")]
-    Bang,
-    #[token("(")]
-    LParen,
-    #[token(")")]
-    RParen,
-    #[token("[")]
-    LBracket,
-    #[token("]")]
-    RBracket,
-    #[token("{")]
-    LBrace,
-    #[token("}")]
-    RBrace,
-    #[token(".")]
-    Dot,
-    #[token(":")]
-    Colon,
-    #[token("?")]
-    QMark,
-    #[token(";")]
-    Semicolon,
-    #[token(",")]
-    Comma,
-    #[token("in")]
-    InKw,
-    #[token("let")]
-    LetKw,
-    #[token("if")]
-    IfKw,
-    #[token("else")]
-    ElseKw,
-    #[token("foreach")]
-    ForeachKw,
-    #[token("continue")]
-    ContinueKw,
-    #[token("break")]
-    BreakKw,
-    #[token("return")]
-    ReturnKw,
-    #[token("true")]
-    TrueKw,
-    #[token("false")]
-    FalseKw,
-    #[token("fn")]
-    FnKw,
-    #[token("import")]
-    ImportKw,
-    #[token("\n")]
-    Newline,
-    #[error]
-    #[regex(r"/\*([^*]|\*+[^*/])*\*?")]
-    Error,
+    let opening = lex.slice();
+    let quote = &opening[opening.len() - 1..];
+    let closing = format!("{}{}", quote, &opening[1..opening.len() - 1]);
+
+    match lex.remainder().find(&closing) {
+        Some(end) => {
+            lex.bump(end + closing.len());
+            true
+        }
+        None => {
+            lex.bump(lex.remainder().len());
+            false
+        }
+    }
 }
-impl From<Tk> for SyntaxKind
+
+/// How the body of a quoted string ended.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum StrEnd
 {
-    fn from(tk: Tk) -> Self
-    {
-        use SyntaxKind::*;
-        match tk {
-            Tk::Ident => T![ident],
-            Tk::IntNumber => T![int_number],
-            Tk::Str => T![str],
-            Tk::MultilineStr => T![multiline_str],
-            Tk::Comment => T![comment],
-            Tk::BlockComment => T![block_comment],
-            Tk::Whitespace => T![whitespace],
-            Tk::Plus => T ! [+],
-            Tk::Minus => T ! [-],
-            Tk::Asterisk => T ! [*],
-            Tk::Slash => T ! [/],
-            Tk::Percent => T ! [%],
-            Tk::RAngle => T ! [>],
-            Tk::LAngle => T ! [<],
-            Tk::Eq => T ! [=],
-            Tk::Bang => T![!],
-            Tk::LParen => T!['('],
-            Tk::RParen => T![')'],
-            Tk::LBracket => T!['['],
-            Tk::RBracket => T![']'],
-            Tk::LBrace => T!['{'],
-            Tk::RBrace => T!['}'],
-            Tk::Dot => T ! [.],
-            Tk::Colon => T ! [:],
-            Tk::QMark => T ! [?],
-            Tk::Semicolon => T ! [;],
-            Tk::Comma => T ! [,],
-            Tk::InKw => T![in],
-            Tk::LetKw => T![let],
-            Tk::IfKw => T![if],
-            Tk::ElseKw => T![else],
-            Tk::ForeachKw => T![for],
-            Tk::ContinueKw => T![continue],
-            Tk::BreakKw => T![break],
-            Tk::ReturnKw => T![return],
-            Tk::TrueKw => T![true],
-            Tk::FalseKw => T![false],
-            Tk::FnKw => T![fn],
-            Tk::ImportKw => T![import],
-            Tk::Newline => T![newline],
-            Tk::Error => ERROR,
+    /// The closing quote.
+    Quote,
+    /// The `${` opening an interpolation.
+    Interpolation,
+    /// The end of the input.
+    Eof,
+}
+
+/// Scans the body of a string delimited by `quote`, returning how long it is
+/// up to and including the closing quote or the `${` that opens an
+/// interpolation.
+fn scan_str(text: &str, quote: char) -> (usize, StrEnd)
+{
+    let mut chars = text.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '$' if text[i + 1..].starts_with('{') => {
+                return (i + 2, StrEnd::Interpolation)
+            }
+            c if c == quote => return (i + 1, StrEnd::Quote),
+            _ => {}
         }
     }
+    (text.len(), StrEnd::Eof)
+}
+
+/// Called after the opening quote of a string was matched; eats its body.
+/// A string that opens an interpolation is cut after the `${`, the
+/// [`Lexer`] turns it into a `STR_START`.
+fn lex_str(lex: &mut logos::Lexer<Tk>) -> bool
+{
+    let quote = lex.slice().chars().next().unwrap();
+    let (len, end) = scan_str(lex.remainder(), quote);
+    lex.bump(len);
+    end != StrEnd::Eof
+}
+
+/// Whether `text`, a `//` comment, is a `///` doc comment, and not a
+/// `////` line.
+fn is_doc_comment(text: &str) -> bool
+{
+    text.starts_with("///") && !text.starts_with("////")
 }
+
+/// Whether `text`, a `/* */` comment, is a `/** */` doc comment, and not an
+/// empty `/**/` or a `/***` line.
+fn is_block_doc_comment(text: &str) -> bool
+{
+    text.starts_with("/**") && !text.starts_with("/***") && text != "/**/"
+}
+
+/// An interpolated string the lexer is inside of.
+struct Interpolation
+{
+    quote: char,
+    /// How many `{` are open in the current interpolation.
+    depth: u32,
+}
+
 #[allow(missing_debug_implementations)]
 pub struct Lexer<'a>
 {
-    lexer: logos::SpannedIter<'a, Tk>,
+    lexer:          logos::Lexer<'a, Tk>,
+    interpolations: Vec<Interpolation>,
+    /// Whether only whitespace came before on the current line.
+    at_line_start:  bool,
 }
 impl<'a> Lexer<'a>
 {
-    pub(crate) fn new(s: &'a str) -> Self
+    pub fn new(s: &'a str) -> Self
+    {
+        let lexer = Tk::lexer(s);
+        Self { lexer,
+               interpolations: vec![],
+               at_line_start: true }
+    }
+
+    /// Picks the kind of `token`, keeping track of the interpolated strings
+    /// along the way. The `}` closing an interpolation is glued to the rest of
+    /// the string.
+    fn kind(&mut self, token: Tk) -> SyntaxKind
+    {
+        match (token, self.interpolations.last_mut()) {
+            (Tk::Str, _) if self.lexer.slice().ends_with("${") => {
+                let quote = self.lexer.slice().chars().next().unwrap();
+                self.interpolations.push(Interpolation { quote, depth: 0 });
+                STR_START
+            }
+            (Tk::LCurly, Some(interpolation)) => {
+                interpolation.depth += 1;
+                L_CURLY
+            }
+            (Tk::RCurly, Some(interpolation)) if interpolation.depth > 0 => {
+                interpolation.depth -= 1;
+                R_CURLY
+            }
+            (Tk::RCurly, Some(interpolation)) => {
+                let (len, end) =
+                    scan_str(self.lexer.remainder(), interpolation.quote);
+                self.lexer.bump(len);
+                match end {
+                    StrEnd::Interpolation => STR_MIDDLE,
+                    StrEnd::Quote => {
+                        self.interpolations.pop();
+                        STR_END
+                    }
+                    StrEnd::Eof => {
+                        self.interpolations.pop();
+                        ERROR
+                    }
+                }
+            }
+            // a doc comment takes the newline after it, so it isn't taken
+            // for the end of a statement and goes with the item on the next
+            // line. Only comments on lines of their own document anything.
+            (Tk::Comment, _)
+                if self.at_line_start && is_doc_comment(self.lexer.slice()) =>
+            {
+                self.eat_newline();
+                DOC_COMMENT
+            }
+            (Tk::BlockComment, _)
+                if self.at_line_start
+                   && is_block_doc_comment(self.lexer.slice()) =>
+            {
+                self.eat_newline();
+                BLOCK_DOC_COMMENT
+            }
+            // only the first line of a file can be a shebang, as in
+            // `#!/usr/bin/env lfr`. It runs to the end of the line.
+            (Tk::Error, _)
+                if self.lexer.span().start == 0
+                   && self.lexer.source().starts_with("#!") =>
+            {
+                let rest = self.lexer.remainder();
+                let len = rest.find(['\r', '\n']).unwrap_or(rest.len());
+                self.lexer.bump(len);
+                SHEBANG
+            }
+            (token, _) => token.into(),
+        }
+    }
+
+    fn eat_newline(&mut self)
     {
-        let lexer = Tk::lexer(s).spanned();
-        Self { lexer }
+        let remainder = self.lexer.remainder();
+        if remainder.starts_with("\r\n") {
+            self.lexer.bump(2);
+        }
+        else if remainder.starts_with('\n') {
+            self.lexer.bump(1);
+        }
     }
 }
 impl<'a> Iterator for Lexer<'a>
@@ -163,13 +201,20 @@ impl<'a> Iterator for Lexer<'a>
 
     fn next(&mut self) -> Option<Self::Item>
     {
-        self.lexer.next().map(|(token, span)| {
-                             (token.into(),
-                              span.with(|it| -> Range<u32> {
-                                      it.start.try_into().unwrap()
-                                      ..it.end.try_into().unwrap()
-                                  })
-                                  .into())
-                         })
+        let token = self.lexer.next()?;
+        let kind = self.kind(token);
+        self.at_line_start = match kind {
+            NEWLINE => true,
+            DOC_COMMENT | BLOCK_DOC_COMMENT => {
+                self.lexer.slice().ends_with('\n')
+            }
+            WHITESPACE => self.at_line_start,
+            _ => false,
+        };
+        let span = self.lexer.span().with(|it| -> Range<u32> {
+                                        it.start.try_into().unwrap()
+                                        ..it.end.try_into().unwrap()
+                                    });
+        Some((kind, span.into()))
     }
 }
@@ -0,0 +1,182 @@
+// This file was generated by gen_syntax.
+
+#![allow(unused_imports)]
+
+use lfr_syntax::SyntaxKind::{
+    self,
+    *,
+};
+use logos::Logos;
+/// The tokens `logos` splits the source into.
+#[derive(Logos, Copy, Clone, Debug, PartialEq, PartialOrd, Eq)]
+pub enum Tk
+{
+    #[token(";")]
+    Semicolon,
+    #[token(",")]
+    Comma,
+    #[token("(")]
+    LParen,
+    #[token(")")]
+    RParen,
+    #[token("{")]
+    LCurly,
+    #[token("}")]
+    RCurly,
+    #[token("[")]
+    LBrack,
+    #[token("]")]
+    RBrack,
+    #[token("<")]
+    LAngle,
+    #[token(">")]
+    RAngle,
+    #[token(".")]
+    Dot,
+    #[token(":")]
+    Colon,
+    #[token("?")]
+    Qmark,
+    #[token("=")]
+    Eq,
+    #[token("+")]
+    Plus,
+    #[token("-")]
+    Minus,
+    #[token("*")]
+    Asterisk,
+    #[token("/")]
+    Slash,
+    #[token("%")]
+    Percent,
+    #[token("&")]
+    Amp,
+    #[token("|")]
+    Pipe,
+    #[token("^")]
+    Caret,
+    #[token("!")]
+    Bang,
+    #[token("~")]
+    Tilde,
+    #[token("@")]
+    At,
+    #[token("_")]
+    Underscore,
+    #[token("fn")]
+    FnKw,
+    #[token("while")]
+    WhileKw,
+    #[token("for")]
+    ForKw,
+    #[token("continue")]
+    ContinueKw,
+    #[token("break")]
+    BreakKw,
+    #[token("return")]
+    ReturnKw,
+    #[token("if")]
+    IfKw,
+    #[token("else")]
+    ElseKw,
+    #[token("let")]
+    LetKw,
+    #[token("true")]
+    TrueKw,
+    #[token("false")]
+    FalseKw,
+    #[token("import")]
+    ImportKw,
+    #[token("struct")]
+    StructKw,
+    #[token("enum")]
+    EnumKw,
+    #[token("match")]
+    MatchKw,
+    #[regex("[\\p{XID_Start}_]\\p{XID_Continue}*")]
+    Ident,
+    #[regex("([1-9][0-9]*|0x[0-9a-fA-F]+|0b[01]+|0[0-7]+|0)[uU]?[lL]?")]
+    IntNumber,
+    #[regex("[0-9]+(\\.[0-9]+([eE][+-]?[0-9]+)?|[eE][+-]?[0-9]+)")]
+    FloatNumber,
+    #[regex("c'(\\\\(u\\{[^}'\\n]*\\}|[^\\n])|[^'\\\\\\n])'")]
+    Char,
+    #[regex("['\"]", super::lex_str)]
+    Str,
+    #[regex("'''([^']|'[^']|''[^'])*'''")]
+    MultilineStr,
+    #[regex("r#*['\"]", super::lex_raw_str)]
+    RawStr,
+    #[regex("//[^\\n]*")]
+    Comment,
+    #[regex("/\\*([^*]|\\**[^*/])*\\*+/")]
+    BlockComment,
+    #[regex("[ \\t\\r]+")]
+    Whitespace,
+    #[regex("\\n")]
+    Newline,
+    #[error]
+    #[regex("/\\*([^*]|\\*+[^*/])*\\*?")]
+    Error,
+}
+impl From<Tk> for SyntaxKind
+{
+    fn from(tk: Tk) -> Self
+    {
+        match tk {
+            Tk::Semicolon => SEMICOLON,
+            Tk::Comma => COMMA,
+            Tk::LParen => L_PAREN,
+            Tk::RParen => R_PAREN,
+            Tk::LCurly => L_CURLY,
+            Tk::RCurly => R_CURLY,
+            Tk::LBrack => L_BRACK,
+            Tk::RBrack => R_BRACK,
+            Tk::LAngle => L_ANGLE,
+            Tk::RAngle => R_ANGLE,
+            Tk::Dot => DOT,
+            Tk::Colon => COLON,
+            Tk::Qmark => QMARK,
+            Tk::Eq => EQ,
+            Tk::Plus => PLUS,
+            Tk::Minus => MINUS,
+            Tk::Asterisk => ASTERISK,
+            Tk::Slash => SLASH,
+            Tk::Percent => PERCENT,
+            Tk::Amp => AMP,
+            Tk::Pipe => PIPE,
+            Tk::Caret => CARET,
+            Tk::Bang => BANG,
+            Tk::Tilde => TILDE,
+            Tk::At => AT,
+            Tk::Underscore => UNDERSCORE,
+            Tk::FnKw => FN_KW,
+            Tk::WhileKw => WHILE_KW,
+            Tk::ForKw => FOR_KW,
+            Tk::ContinueKw => CONTINUE_KW,
+            Tk::BreakKw => BREAK_KW,
+            Tk::ReturnKw => RETURN_KW,
+            Tk::IfKw => IF_KW,
+            Tk::ElseKw => ELSE_KW,
+            Tk::LetKw => LET_KW,
+            Tk::TrueKw => TRUE_KW,
+            Tk::FalseKw => FALSE_KW,
+            Tk::ImportKw => IMPORT_KW,
+            Tk::StructKw => STRUCT_KW,
+            Tk::EnumKw => ENUM_KW,
+            Tk::MatchKw => MATCH_KW,
+            Tk::Ident => IDENT,
+            Tk::IntNumber => INT_NUMBER,
+            Tk::FloatNumber => FLOAT_NUMBER,
+            Tk::Char => CHAR,
+            Tk::Str => STR,
+            Tk::MultilineStr => MULTILINE_STR,
+            Tk::RawStr => RAW_STR,
+            Tk::Comment => COMMENT,
+            Tk::BlockComment => BLOCK_COMMENT,
+            Tk::Whitespace => WHITESPACE,
+            Tk::Newline => NEWLINE,
+            Tk::Error => ERROR,
+        }
+    }
+}
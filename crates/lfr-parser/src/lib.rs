@@ -1,4 +1,9 @@
 pub extern crate lfr_syntax;
 
+#[cfg(any(test, feature = "fuzz"))]
+pub mod fuzz;
 pub mod lexer;
 pub mod parser;
+
+#[cfg(test)]
+mod tests;
@@ -1,34 +1,47 @@
 pub mod error;
 mod event;
 mod marker;
+pub mod reparsing;
 pub mod text_token_source;
 pub mod text_tree_sink;
+mod token_set;
 pub mod token_source;
 pub mod tree_sink;
 
-use lfr_stdx::CopyTo;
+use std::cell::Cell;
+use std::fmt::Write;
+
+use lfr_syntax::SyntaxKind::{
+    self,
+    *,
+};
+use lfr_syntax::ast::{
+    self,
+    AstNode,
+};
 use lfr_syntax::rowan::{
     GreenNode,
     NodeOrToken,
+    TextRange,
     TextSize,
 };
-use lfr_syntax::SyntaxKind::{
-    self,
-    *,
-};
 use lfr_syntax::{
     SyntaxNode,
     T,
 };
 use text_token_source::LexerWrap;
 
-use self::error::ParseError;
+use self::error::{
+    ErrorCode,
+    ParseError,
+};
 use self::event::Event;
 use self::marker::{
     CompletedMarker,
     Marker,
 };
 use self::text_tree_sink::TextTreeSink;
+use self::token_set::TokenSet;
 use self::token_source::{
     FindProperty,
     ForwardToken,
@@ -50,14 +63,85 @@ impl IsTrivia for SyntaxKind
         self == Self::WHITESPACE
         || self == Self::COMMENT
         || self == Self::BLOCK_COMMENT
+        || self == Self::DOC_COMMENT
+        || self == Self::BLOCK_DOC_COMMENT
     }
 }
 
+/// How many lookaheads a parse may take before it is considered stuck. Only
+/// debug builds check it, so that tests and fuzzing catch a loop that never
+/// bumps, while a large input never panics in a release build.
+const STEP_LIMIT: u32 = 15_000_000;
+
+/// How deeply expressions, patterns and types may nest. The parser recurses
+/// into each level, a deeper input would overflow the stack: a debug build
+/// takes about 12 KiB a level, which leaves room within the 2 MiB of a
+/// spawned thread.
+pub const NESTING_LIMIT: u32 = 64;
+
+/// The keywords that start a statement but can't start an expression.
+const STMT_KW: TokenSet = TokenSet::new(&[T![let],
+                                          T![for],
+                                          T![while],
+                                          T![struct],
+                                          T![enum],
+                                          T![import],
+                                          T![continue],
+                                          T![break],
+                                          T![return]]);
+
+/// Where parsing resumes after a bad statement.
+const STMT_RECOVERY: TokenSet =
+    STMT_KW.union(TokenSet::new(&[T![newline], T!['}']]));
+
+/// Where parsing resumes after a bad expression.
+const EXPR_RECOVERY: TokenSet =
+    STMT_RECOVERY.union(TokenSet::new(&[T![')'], T![']'], T![,]]));
+
+/// Where a bracketed list gives up, assuming it was never closed.
+const LIST_RECOVERY: TokenSet =
+    STMT_KW.union(TokenSet::new(&[T![')'], T![']'], T!['}']]));
+
+/// Where parsing resumes after a bad pattern.
+const PAT_RECOVERY: TokenSet =
+    LIST_RECOVERY.union(TokenSet::new(&[T![in], T![,], T!['{'], T![newline]]));
+
+/// The brackets an expression or a pattern nested too deep is skipped to the
+/// end of.
+const EXPR_BRACKETS: &[(SyntaxKind, SyntaxKind)] = &[(T!['('], T![')']),
+                                                     (T!['['], T![']']),
+                                                     (T!['{'], T!['}']),
+                                                     (T![str_start],
+                                                      T![str_end])];
+
+/// The brackets the operand of a ternary nested too deep is skipped to the
+/// end of, each `?` has its `:`.
+const TERNARY_BRACKETS: &[(SyntaxKind, SyntaxKind)] = &[(T!['('], T![')']),
+                                                        (T!['['], T![']']),
+                                                        (T!['{'], T!['}']),
+                                                        (T![str_start],
+                                                         T![str_end]),
+                                                        (T![?], T![:])];
+
+/// The brackets a type nested too deep is skipped to the end of.
+const TYPE_BRACKETS: &[(SyntaxKind, SyntaxKind)] = &[(T!['('], T![')']),
+                                                     (T!['['], T![']']),
+                                                     (T!['{'], T!['}']),
+                                                     (T![str_start],
+                                                      T![str_end]),
+                                                     (T![<], T![>])];
+
 pub(crate) struct Parser<'ts>
 {
     /// tokens
     source: &'ts mut dyn TokenSource,
     events: Vec<Event>,
+    /// lookaheads taken so far, a loop that never bumps shows up here.
+    steps:  Cell<u32>,
+    /// raw tokens bumped so far.
+    pos:    usize,
+    /// levels of nesting being parsed, see [`Parser::nested`].
+    depth:  u32,
 }
 
 #[allow(clippy::inline_always)]
@@ -66,45 +150,68 @@ impl<'ts> Parser<'ts>
     fn parse(&mut self) { parse_root(self); }
 
     #[inline(always)]
-    fn at(&self, kind: SyntaxKind) -> bool
+    fn at(&self, kind: SyntaxKind) -> bool { self.nth_at(0, kind) }
+
+    /// Whether the token `n` ahead is `kind`, gluing composites. A `_` is
+    /// an identifier too, patterns and types check for it first to take it
+    /// for a placeholder.
+    fn nth_at(&self, n: usize, kind: SyntaxKind) -> bool
     {
         // TAG: composites
         match kind {
-            T![&&] => self.at_composite2(T![&], T![&]),
-            T![||] => self.at_composite2(T![|], T![|]),
-            T![+=] => self.at_composite2(T![+], T![=]),
-            T![-=] => self.at_composite2(T![-], T![=]),
-            T![*=] => self.at_composite2(T![*], T![=]),
-            T![/=] => self.at_composite2(T![/], T![=]),
-            T![%=] => self.at_composite2(T![%], T![=]),
-            T![&=] => self.at_composite2(T![&], T![=]),
-            T![|=] => self.at_composite2(T![|], T![=]),
-            T![^=] => self.at_composite2(T![^], T![=]),
-            T![&&=] => self.at_composite3(T![&], T![&], T![=]),
-            T![||=] => self.at_composite3(T![|], T![|], T![=]),
-            T![==] => self.at_composite2(T![=], T![=]),
-            T![!=] => self.at_composite2(T![!], T![=]),
-            T![<=] => self.at_composite2(T![<], T![=]),
-            T![>=] => self.at_composite2(T![>], T![=]),
-            T![::] => self.at_composite2(T![:], T![:]),
-            kind => self.current() == kind,
-        }
-    }
-
-    fn at_composite2(&self, kind1: SyntaxKind, kind2: SyntaxKind) -> bool
+            T![ident] => matches!(self.nth(n), T![ident] | T![_]),
+            T![&&] => self.at_composite2(n, T![&], T![&]),
+            T![||] => self.at_composite2(n, T![|], T![|]),
+            T![+=] => self.at_composite2(n, T![+], T![=]),
+            T![-=] => self.at_composite2(n, T![-], T![=]),
+            T![*=] => self.at_composite2(n, T![*], T![=]),
+            T![/=] => self.at_composite2(n, T![/], T![=]),
+            T![%=] => self.at_composite2(n, T![%], T![=]),
+            T![&=] => self.at_composite2(n, T![&], T![=]),
+            T![|=] => self.at_composite2(n, T![|], T![=]),
+            T![^=] => self.at_composite2(n, T![^], T![=]),
+            T![&&=] => self.at_composite3(n, T![&], T![&], T![=]),
+            T![||=] => self.at_composite3(n, T![|], T![|], T![=]),
+            T![==] => self.at_composite2(n, T![=], T![=]),
+            T![!=] => self.at_composite2(n, T![!], T![=]),
+            T![<=] => self.at_composite2(n, T![<], T![=]),
+            T![>=] => self.at_composite2(n, T![>], T![=]),
+            T![::] => self.at_composite2(n, T![:], T![:]),
+            T![..] => self.at_composite2(n, T![.], T![.]),
+            T![..=] => self.at_composite3(n, T![.], T![.], T![=]),
+            T![->] => self.at_composite2(n, T![-], T![>]),
+            T![=>] => self.at_composite2(n, T![=], T![>]),
+            T![|>] => self.at_composite2(n, T![|], T![>]),
+            kind => self.nth(n) == kind,
+        }
+    }
+
+    fn at_composite2(&self,
+                     n: usize,
+                     kind1: SyntaxKind,
+                     kind2: SyntaxKind)
+                     -> bool
     {
-        self.current() == kind1 && self.nth(1) == kind2
+        self.nth(n) == kind1
+        && self.source.is_joint_to_next(n)
+        && self.nth(n + 1) == kind2
     }
 
     fn at_composite3(&self,
+                     n: usize,
                      kind1: SyntaxKind,
                      kind2: SyntaxKind,
                      kind3: SyntaxKind)
                      -> bool
     {
-        self.current() == kind1 && self.nth(1) == kind2 && self.nth(2) == kind3
+        self.at_composite2(n, kind1, kind2)
+        && self.source.is_joint_to_next(n + 1)
+        && self.nth(n + 2) == kind3
     }
 
+    #[inline(always)]
+    fn at_ts(&self, set: TokenSet) -> bool { set.contains(self.current()) }
+
     #[inline(always)]
     fn at_any<I>(&self, kinds: I) -> Option<SyntaxKind>
         where I: IntoIterator<Item = SyntaxKind>
@@ -129,20 +236,35 @@ impl<'ts> Parser<'ts>
         self.do_bump(kind, Parser::sk_raw_tokens(kind));
     }
 
+    /// Bumps the current token as a `kind` token, such as a contextual
+    /// keyword that is a name where it stands.
+    fn bump_remap(&mut self, kind: SyntaxKind)
+    {
+        if self.current() == EOF {
+            return
+        }
+
+        self.do_bump(kind, 1);
+    }
+
     fn do_bump(&mut self, kind: SyntaxKind, n_raw_tokens: u8)
     {
         assert!(n_raw_tokens <= 3);
         for _ in 0..n_raw_tokens {
             self.source.bump();
         }
+        self.pos += usize::from(n_raw_tokens);
         self.push_event(Event::Token { kind, n_raw_tokens })
     }
 
     fn expect_failed(&mut self, kind: SyntaxKind)
     {
-        self.error(format!("expected {:?}, got {:?}",
-                           kind,
-                           self.current()));
+        let msg = format!("expected {:?}, got {:?}",
+                          kind,
+                          self.current());
+        let err = self.err_here(ErrorCode::ExpectedToken, msg)
+                      .with_expected(&[kind]);
+        self.error(err);
     }
 
     fn expect(&mut self, kind: SyntaxKind) -> bool
@@ -154,12 +276,22 @@ impl<'ts> Parser<'ts>
         false
     }
 
-    pub(crate) fn error<E: Into<ParseError>>(&mut self, msg: E)
+    pub(crate) fn error(&mut self, msg: ParseError)
     {
-        let msg = msg.into();
         self.push_event(Event::Error { msg })
     }
 
+    /// An error about the current token.
+    fn err_here(&self, code: ErrorCode, msg: impl Into<String>) -> ParseError
+    {
+        ParseError::new(code, self.current_range(), msg)
+    }
+
+    fn current_range(&self) -> TextRange { self.source.token_range(self.pos) }
+
+    /// The range of the last token bumped.
+    fn prev_range(&self) -> TextRange { self.source.token_range(self.pos - 1) }
+
     #[inline(always)]
     fn sk_raw_tokens(kind: SyntaxKind) -> u8
     {
@@ -182,6 +314,11 @@ impl<'ts> Parser<'ts>
             T![<=] => 2,
             T![>=] => 2,
             T![::] => 2,
+            T![..] => 2,
+            T![..=] => 3,
+            T![->] => 2,
+            T![=>] => 2,
+            T![|>] => 2,
             _ => 1,
         }
     }
@@ -198,25 +335,17 @@ impl<'ts> Parser<'ts>
         }
     }
 
-    #[inline(always)]
-    fn eat_any<I>(&mut self, kinds: I) -> bool
-        where I: IntoIterator<Item = SyntaxKind>
-    {
-        if let Some(kind) = self.at_any(kinds) {
-            self.do_bump(kind, Parser::sk_raw_tokens(kind));
-            true
-        }
-        else {
-            false
-        }
-    }
-
     #[inline(always)]
     fn current(&self) -> SyntaxKind { self.nth(0) }
 
     #[inline(always)]
     fn nth(&self, n: usize) -> SyntaxKind
     {
+        let steps = self.steps.get();
+        debug_assert!(steps <= STEP_LIMIT,
+                      "the parser seems stuck");
+        self.steps.set(steps + 1);
+
         self.source.lookahead(n).syntax_kind
     }
 
@@ -232,11 +361,9 @@ impl<'ts> Parser<'ts>
         }
 
         self.expect_failed(T![newline]);
+        self.recover(STMT_RECOVERY);
     }
 
-    #[inline(always)]
-    fn has_newline(&self) -> bool { self.at(T![newline]) }
-
     #[inline(always)]
     fn bump_to(&mut self, forward_token: ForwardToken)
     {
@@ -255,24 +382,15 @@ impl<'ts> Parser<'ts>
     #[inline(always)]
     fn bump_to_if_next_non_newline_is(&mut self, kind: SyntaxKind) -> bool
     {
-        let mut tk = ForwardToken::default();
-        let k = self.next_not_newline().copy_to(&mut tk).kind;
-        if k == kind {
-            self.bump_to(tk);
-            true
-        }
-        else {
-            false
-        }
+        self.bump_to_if_next_non_newline_is_any([kind])
     }
 
     #[inline(always)]
     fn bump_to_if_next_non_newline_is_any<I>(&mut self, kinds: I) -> bool
         where I: IntoIterator<Item = SyntaxKind>
     {
-        let mut tk = ForwardToken::default();
-        let k = self.next_not_newline().copy_to(&mut tk).kind;
-        if kinds.into_iter().any(|it| it == k) {
+        let tk = self.next_not_newline();
+        if kinds.into_iter().any(|it| self.nth_at(tk.offset, it)) {
             self.bump_to(tk);
             true
         }
@@ -281,6 +399,14 @@ impl<'ts> Parser<'ts>
         }
     }
 
+    /// The first of `kinds` that the next token that isn't a newline is.
+    fn next_non_newline_at_any<I>(&self, kinds: I) -> Option<SyntaxKind>
+        where I: IntoIterator<Item = SyntaxKind>
+    {
+        let tk = self.next_not_newline();
+        kinds.into_iter().find(|it| self.nth_at(tk.offset, *it))
+    }
+
     #[inline(always)]
     fn skip_newlines(&mut self)
     {
@@ -289,15 +415,103 @@ impl<'ts> Parser<'ts>
         }
     }
 
-    #[inline(always)]
-    fn unexpected(&mut self)
+    /// Reports the current token and wraps it, along with the tokens up to
+    /// the next one in `recovery`, in an `ERROR` node.
+    fn unexpected(&mut self, recovery: TokenSet)
     {
         let current = self.current();
+        let mut err = self.err_here(ErrorCode::UnexpectedToken,
+                                    format!("Unexpected {:?}", current));
         if current != EOF {
-            self.do_bump(current, Parser::sk_raw_tokens(current));
+            let marker = self.start();
+            self.bump_any();
+            self.skip_to(recovery);
+            marker.complete(self, ERROR);
+            err.range = err.range.cover(self.prev_range());
         }
+        self.error(err);
+    }
 
-        self.error(format!("Unexpected {:?}", current));
+    /// Reports `err`, then wraps the tokens up to the next one in `recovery`
+    /// in an `ERROR` node, if there are any. The error covers them.
+    fn err_recover(&mut self, mut err: ParseError, recovery: TokenSet)
+    {
+        if let Some(range) = self.recover(recovery) {
+            err.range = err.range.cover(range);
+        }
+        self.error(err);
+    }
+
+    /// Wraps the tokens up to the next one in `recovery` in an `ERROR` node,
+    /// if there are any, returning their range. Whoever gave up on them
+    /// reported why.
+    fn recover(&mut self, recovery: TokenSet) -> Option<TextRange>
+    {
+        if self.at(EOF) || self.at_ts(recovery) {
+            return None
+        }
+
+        let start = self.current_range().start();
+        let marker = self.start();
+        self.skip_to(recovery);
+        marker.complete(self, ERROR);
+        Some(TextRange::new(start, self.prev_range().end()))
+    }
+
+    fn skip_to(&mut self, recovery: TokenSet)
+    {
+        while !self.at(EOF) && !self.at_ts(recovery) {
+            self.bump_any();
+        }
+    }
+
+    /// Runs `parse` one level of nesting deeper. Past [`NESTING_LIMIT`]
+    /// levels, reports the nesting instead and wraps what would have nested
+    /// in an `ERROR` node, up to the end of its `brackets`.
+    fn nested(&mut self,
+              brackets: &[(SyntaxKind, SyntaxKind)],
+              parse: impl FnOnce(&mut Self))
+    {
+        if self.depth < NESTING_LIMIT {
+            self.depth += 1;
+            parse(self);
+            self.depth -= 1;
+            return
+        }
+
+        let msg = format!("nested more than {} levels deep",
+                          NESTING_LIMIT);
+        let mut err = self.err_here(ErrorCode::NestingTooDeep, msg);
+        let marker = self.start();
+        let start = self.pos;
+        // a closing bracket closes the ones left open inside it too.
+        let mut closers = vec![];
+        while !self.at(EOF) {
+            let kind = self.current();
+            if let Some(&(_, close)) = brackets.iter().find(|it| it.0 == kind) {
+                closers.push(close);
+            }
+            else if let Some(i) = closers.iter().rposition(|it| *it == kind) {
+                closers.truncate(i);
+            }
+            else if closers.is_empty()
+                      && (kind == T![str_middle]
+                          || self.at_ts(EXPR_RECOVERY)
+                          || brackets.iter().any(|it| it.1 == kind))
+            {
+                break
+            }
+            self.bump_any();
+        }
+
+        if self.pos == start {
+            marker.abandon(self);
+        }
+        else {
+            marker.complete(self, ERROR);
+            err.range = err.range.cover(self.prev_range());
+        }
+        self.error(err);
     }
 
     #[inline(always)]
@@ -311,26 +525,242 @@ impl<'ts> Parser<'ts>
     #[inline(always)]
     fn push_event(&mut self, event: Event) { self.events.push(event) }
 }
-///
-pub fn parse(text: &str) -> (GreenNode, Vec<(ParseError, TextSize)>)
+/// Parses `text` into a green tree, returning it along with the errors.
+pub fn parse(text: &str) -> (GreenNode, Vec<ParseError>)
 {
-    let lexer = Lexer::new(text);
-    let tokens: Vec<_> =
-        lexer.map(|(kind, span)| Token { syntax_kind: kind,
-                                         len:         span.text_range.len(), })
-             .collect();
+    let tokens = lex(text);
     let mut lexer = LexerWrap::new(&tokens);
     let mut sink = TextTreeSink::new(text, &tokens);
 
     parse_to_sink(&mut lexer, &mut sink);
-    sink.finish()
+    let (green, mut errors) = sink.finish();
+    validate_string_lits(&SyntaxNode::new_root(green.clone()),
+                         &mut errors);
+    (green, errors)
+}
+
+/// What the fragment parsers return: the node along with the errors, or all
+/// the errors if there are tokens left after the node.
+///
+/// The node hangs off a `ROOT` that holds the newlines around it, so its
+/// range is an offset into the text.
+pub type FragmentResult<N> = Result<(N, Vec<ParseError>), Vec<ParseError>>;
+
+/// Parses `text` as a single expression.
+pub fn parse_expr_fragment(text: &str) -> FragmentResult<ast::Expr>
+{
+    let (node, errors) = parse_fragment(text, parse_expr)?;
+    Ok((ast::Expr::cast(node).expect("an expression"), errors))
+}
+
+/// Parses `text` as a single statement.
+pub fn parse_stmt_fragment(text: &str) -> FragmentResult<ast::Stmt>
+{
+    let (node, errors) = parse_fragment(text, parse_statement)?;
+    Ok((ast::Stmt::cast(node).expect("a statement"), errors))
+}
+
+/// Parses `text` as a single `{ ... }` block.
+pub fn parse_block_fragment(text: &str) -> FragmentResult<ast::Block>
+{
+    let (node, errors) = parse_fragment(text, |p| {
+        parse_expr_block(p);
+    })?;
+    Ok((ast::Block::cast(node).expect("a block"), errors))
+}
+
+/// Parses `text` as a single type, like the one after the `:` of a `let`.
+pub fn parse_type_fragment(text: &str) -> FragmentResult<ast::TypeRef>
+{
+    let (node, errors) = parse_fragment(text, |p| {
+        parse_type_ref(p);
+    })?;
+    Ok((ast::TypeRef::cast(node).expect("a type"), errors))
+}
+
+/// Runs `parse_fn` over `text` under a `ROOT`, returning the node it made.
+/// Fails with a [`ErrorCode::TrailingTokens`] error, after the others, if
+/// it didn't read `text` to the end.
+fn parse_fragment(text: &str,
+                  parse_fn: impl FnOnce(&mut Parser))
+                  -> FragmentResult<SyntaxNode>
+{
+    let tokens = lex(text);
+    let mut lexer = LexerWrap::new(&tokens);
+    let mut p = Parser { source: &mut lexer,
+                         events: vec![],
+                         steps:  Cell::new(0),
+                         pos:    0,
+                         depth:  0, };
+    let marker = p.start();
+    p.skip_newlines();
+    parse_fn(&mut p);
+    p.skip_newlines();
+    let trailing =
+        (!p.at(EOF)).then(|| {
+                        let range =
+                            TextRange::new(p.current_range().start(),
+                                           TextSize::of(text.trim_end()));
+                        let msg = format!("expected the end of the fragment, \
+                                           got {:?}",
+                                          p.current());
+                        ParseError::new(ErrorCode::TrailingTokens, range, msg)
+                    });
+    p.skip_to(TokenSet::EMPTY);
+    marker.complete(&mut p, ROOT);
+
+    let events = p.events;
+    let mut sink = TextTreeSink::new(text, &tokens);
+    event::process(&mut sink, events);
+    let (green, mut errors) = sink.finish();
+    let root = SyntaxNode::new_root(green);
+    validate_string_lits(&root, &mut errors);
+    if let Some(err) = trailing {
+        errors.push(err);
+        return Err(errors)
+    }
+    let node = root.first_child().expect("the fragment");
+    Ok((node, errors))
+}
+
+/// Parses `text` on its own as a node of `kind`, which must be a `BLOCK`, a
+/// `TUPLE_EXPR` or an `ARR_EXPR`. Gives up unless `text` lexes into a single
+/// balanced node that the parser reads to the end: only then does it lex and
+/// parse the same wherever it sits in a file. Gives up too if the node nests
+/// past [`NESTING_LIMIT`] when it starts `depth` levels deep, and `depth`
+/// must be at least as deep as the node sits in the file: only then does it
+/// nest within the limit there too.
+pub(crate) fn parse_node(text: &str,
+                         kind: SyntaxKind,
+                         depth: u32)
+                         -> Option<(GreenNode, Vec<ParseError>)>
+{
+    let (parse_fn, open, close): (fn(&mut Parser) -> CompletedMarker, _, _) =
+        match kind {
+            BLOCK => (parse_expr_block, T!['{'], T!['}']),
+            TUPLE_EXPR => (parse_tuple_expr, T!['('], T![')']),
+            ARR_EXPR => (parse_array_expr, T!['['], T![']']),
+            _ => return None,
+        };
+    let tokens = lex(text);
+    if !is_balanced(&tokens, open, close) {
+        return None
+    }
+
+    let mut lexer = LexerWrap::new(&tokens);
+    let mut p = Parser { source: &mut lexer,
+                         events: vec![],
+                         steps:  Cell::new(0),
+                         pos:    0,
+                         depth };
+    parse_fn(&mut p);
+    if !p.at(EOF) {
+        return None
+    }
+    let events = p.events;
+    let mut sink = TextTreeSink::new(text, &tokens);
+    event::process(&mut sink, events);
+    let (green, mut errors) = sink.finish();
+    if errors.iter().any(|it| it.code == ErrorCode::NestingTooDeep) {
+        return None
+    }
+    validate_string_lits(&SyntaxNode::new_root(green.clone()),
+                         &mut errors);
+    Some((green, errors))
+}
+
+/// Whether `tokens` open with `open` and are closed by the matching `close`
+/// at the very end. The curly braces and the interpolated strings in between
+/// must be balanced too, or the lexer would read them differently inside an
+/// interpolation.
+fn is_balanced(tokens: &[Token], open: SyntaxKind, close: SyntaxKind) -> bool
+{
+    let ends =
+        tokens.first()
+              .zip(tokens.last())
+              .map(|(first, last)| (first.syntax_kind, last.syntax_kind));
+    if ends != Some((open, close)) {
+        return false
+    }
+
+    let mut depth = 0;
+    let mut curlies = 0;
+    let mut strs = 0;
+    for (i, kind) in tokens.iter().map(|it| it.syntax_kind).enumerate() {
+        if kind == open {
+            depth += 1;
+        }
+        else if kind == close {
+            depth -= 1;
+            if depth == 0 && i != tokens.len() - 1 {
+                return false
+            }
+        }
+        match kind {
+            T!['{'] => curlies += 1,
+            T!['}'] if curlies == 0 => return false,
+            T!['}'] => curlies -= 1,
+            T![str_start] => strs += 1,
+            T![str_end] => strs -= 1,
+            _ => {}
+        }
+    }
+    depth == 0 && curlies == 0 && strs == 0
+}
+
+/// Lexes `text`, telling the contextual keywords apart from the other
+/// identifiers for the parser.
+fn lex(text: &str) -> Vec<Token>
+{
+    Lexer::new(text).map(|(kind, span)| {
+                        let range = span.text_range;
+                        let kind = match kind {
+                            IDENT => {
+                                let text = &text[range];
+                                SyntaxKind::from_contextual_keyword(text)
+                                    .unwrap_or(IDENT)
+                            }
+                            kind => kind,
+                        };
+                        Token { syntax_kind: kind,
+                                len:         range.len(), }
+                    })
+                    .collect()
+}
+
+/// Reports the invalid escapes in the string and char literals under `root`.
+fn validate_string_lits(root: &SyntaxNode, errors: &mut Vec<ParseError>)
+{
+    for node in root.descendants() {
+        let escape_errors =
+            if let Some(lit) = ast::StringLit::cast(node.clone()) {
+                lit.value().err()
+            }
+            else if let Some(lit) = ast::CharLit::cast(node.clone()) {
+                lit.value().err()
+            }
+            else if let Some(s) = ast::InterpolatedStr::cast(node) {
+                s.parts().err()
+            }
+            else {
+                None
+            };
+        for err in escape_errors.into_iter().flatten() {
+            errors.push(ParseError::new(ErrorCode::InvalidEscape,
+                                        err.range,
+                                        err.kind.to_string()));
+        }
+    }
 }
 
 /// parses `text`
 pub fn parse_to_sink(source: &mut dyn TokenSource, sink: &mut dyn TreeSink)
 {
     let mut p = Parser { source,
-                         events: vec![] };
+                         events: vec![],
+                         steps: Cell::new(0),
+                         pos: 0,
+                         depth: 0 };
     p.parse();
     event::process(sink, p.events);
 }
@@ -339,6 +769,23 @@ fn parse_root(p: &mut Parser)
 {
     let marker = p.start();
 
+    // test shebang_and_pragmas
+    // #!/usr/bin/env lfr
+    // @!lfr('>=0.4')
+    //
+    // @!edition('2024')
+    // import std
+    // x = 1
+    p.eat(T![shebang]);
+
+    loop {
+        p.skip_newlines();
+        if !at_pragma(p) {
+            break
+        }
+        parse_pragma(p);
+    }
+
     loop {
         p.skip_newlines();
         if p.at(EOF) || !p.at(T![import]) {
@@ -376,6 +823,22 @@ fn parse_import_stmt(p: &mut Parser)
     marker.complete(p, IMPORT_STMT);
 }
 
+fn at_pragma(p: &Parser) -> bool { p.at(T![@]) && p.nth_at(1, T![!]) }
+
+/// Parses a file-level `@!name(args)`, on a line of its own.
+fn parse_pragma(p: &mut Parser)
+{
+    let marker = p.start();
+
+    p.bump(T![@]);
+    p.bump(T![!]);
+    parse_attr_body(p);
+
+    p.require_newline();
+
+    marker.complete(p, PRAGMA);
+}
+
 fn parse_import_target(p: &mut Parser)
 {
     let marker = p.start();
@@ -394,16 +857,59 @@ fn parse_ident(p: &mut Parser) { p.expect(IDENT); }
 fn parse_lang_item(p: &mut Parser)
 {
     p.skip_newlines();
+
+    // test err misplaced_pragma
+    // import std
+    // @!edition('2024')
+    // x = 1
+    if at_pragma(p) {
+        let err = p.err_here(ErrorCode::UnexpectedToken,
+                             "pragmas go at the top of the file, before the \
+                              imports");
+        p.error(err);
+        parse_pragma(p);
+        return
+    }
     parse_statement(p);
 }
 
 fn parse_statement(p: &mut Parser)
 {
-    let tok = p.current();
-
+    // test err stray_tokens_after_statement
+    // let x = 1 ) ) 2
+    // let y = 3
+
+    // test err garbage_statement
+    // ) @ # 3
+    // z = 4
+
+    // test doc_comments
+    // /// Compiles `src`.
+    // ///
+    // /// Into an object file.
+    // fn cc(src) {}
+    // /** The app. */
+    // let app = executable('app')
+    // /// Not attached, there is a blank line.
+    //
+    // struct Opts {}
+    // x = 1 /// not a doc comment
+    // {
+    //     /// Kinds.
+    //     enum Kind { Lib }
+    // }
+    // /// Before the attributes.
+    // @deprecated
+    // /// After them.
+    // fn old() {}
     let statement_marker = p.start();
 
-    match tok {
+    // the attributes go in the node of the statement, so it starts before
+    // them.
+    let attributed_marker = p.start();
+    let has_attrs = parse_attrs(p);
+
+    let stmt = match p.current() {
         T!['(']
         | T!['[']
         | T!['{']
@@ -413,39 +919,123 @@ fn parse_statement(p: &mut Parser)
         | T![true]
         | T![false]
         | T![int_number]
-        // | T![float_number]
+        | T![float_number]
+        | T![char]
         | T![ident]
+        | T![_]
+        | T![this]
         | T![str]
         | T![multiline_str]
-        | T![if] => {
+        | T![raw_str]
+        | T![str_start]
+        | T![%]
+        | T![|]
+        | T![fn]
+        | T![if]
+        | T![match] => {
             parse_expr(p);
+            None
         }
-        T![let] => {
-            parse_declaration(p);
+        T![let] => Some(parse_declaration(p)),
+        T![for] => Some(parse_for(p)),
+        T![while] => Some(parse_while(p)),
+        T![struct] => Some(parse_struct_def(p)),
+        T![enum] => Some(parse_enum_def(p)),
+        // like in the grammar, they are primary expressions.
+        T![continue] | T![break] | T![return] => {
+            parse_control_stmt(p).precede(p).complete(p, PRIMARY_EXPR);
+            None
         }
-        T![for] => {
-            parse_for(p);
+        // the end of the block isn't for the attributes to take.
+        T!['}'] | EOF if has_attrs => {
+            let err = p.err_here(ErrorCode::UnexpectedToken,
+                                 "expected a statement after the attributes");
+            p.error(err);
+            None
         }
-        T![while] => {
-            parse_while(p);
+        _ => {
+            p.unexpected(STMT_RECOVERY);
+            None
         }
-        T![continue] | T![break] | T![return] => {
-            parse_control_stmt(p);
+    };
+
+    match stmt {
+        _ if !has_attrs => attributed_marker.abandon(p),
+        Some(stmt) => {
+            let kind = stmt.kind();
+            stmt.undo_completion(p).abandon(p);
+            attributed_marker.complete(p, kind);
         }
-        _ => {
-            p.unexpected();
+        // expressions have no node of their own to hold the attributes.
+        None => {
+            attributed_marker.complete(p, EXPR_STMT);
         }
     }
 
     statement_marker.complete(p, STMT);
 }
 
+/// Parses the attributes before a statement, each optionally on a line of
+/// its own, and returns whether there were any.
+fn parse_attrs(p: &mut Parser) -> bool
+{
+    // test attrs
+    // @deprecated('use compile')
+    // fn cc(src) {}
+    // @platform('linux') @doc('the app', brief = true)
+    // let app = executable('app', srcs)
+    // @platform('windows')
+    // struct Opts { static: bool }
+    // @doc('all the things')
+    // enum Kind { Lib, Exe }
+    // @platform('mac')
+    // app = executable('app', srcs)
+    // @unroll
+    // for src in srcs { cc(src) }
+    // @unroll
+    // while false {}
+
+    // test err attr_without_name
+    // @('x')
+    // fn f() {}
+
+    // test err attr_without_statement
+    // { @platform('linux') }
+    let mut has_attrs = false;
+    while p.at(T![@]) {
+        let marker = p.start();
+        p.bump(T![@]);
+        parse_attr_body(p);
+        marker.complete(p, ATTR);
+        p.skip_newlines();
+        has_attrs = true;
+    }
+    has_attrs
+}
+
+/// Parses the `name(args)` of an attribute or a pragma.
+fn parse_attr_body(p: &mut Parser)
+{
+    p.expect(T![ident]);
+    if p.at(T!['(']) {
+        parse_tt(p,
+                 FN_CALL_ARGS,
+                 T!['('],
+                 Some(T![,]),
+                 T![')'],
+                 LIST_RECOVERY,
+                 parse_farg);
+    }
+}
+
 fn parse_expr(p: &mut Parser)
 {
     // test precedence_parsing
     // x = 1 + 2 * 3 % - 4 ( 5 )
 
-    parse_precedence_9_expr(p);
+    p.nested(EXPR_BRACKETS, |p| {
+         parse_precedence_12_expr(p);
+     });
 }
 
 fn parse_tuple_expr(p: &mut Parser) -> CompletedMarker
@@ -458,7 +1048,23 @@ fn parse_tuple_expr(p: &mut Parser) -> CompletedMarker
              T!['('],
              Some(T![,]),
              T![')'],
-             parse_expr)
+             LIST_RECOVERY,
+             parse_tuple_elem)
+}
+
+fn parse_tuple_elem(p: &mut Parser)
+{
+    p.skip_newlines();
+
+    // the arguments of a call start on the line of the callee, a named
+    // argument on the next line is in a tuple.
+    if p.at(T![ident]) && p.nth_at(1, T![=]) && !p.nth_at(1, T![==]) {
+        let err = p.err_here(ErrorCode::UnexpectedToken,
+                             "named arguments only go in calls, and a \
+                              call's `(` goes on the line of the callee");
+        p.error(err);
+    }
+    parse_expr(p)
 }
 
 fn is_tuple_expr_start(p: &mut Parser) -> bool { p.at(T!['(']) }
@@ -473,15 +1079,69 @@ fn parse_array_expr(p: &mut Parser) -> CompletedMarker
              T!['['],
              Some(T![,]),
              T![']'],
+             LIST_RECOVERY,
              parse_expr)
 }
 
 fn is_array_expr_start(p: &mut Parser) -> bool { p.at(T!['[']) }
 
+fn parse_map_expr(p: &mut Parser) -> CompletedMarker
+{
+    // test map_expr
+    // flags = %{ 'linux': ['-pthread'], 'windows': [] }
+    // env = %{
+    //     'CC': cc,
+    //     'CFLAGS': flags[host],
+    // }
+    // empty = %{}
+
+    // test err map_entry_missing_colon
+    // x = %{ 'a' 1, 'b': 2 }
+    p.skip_newlines();
+    assert!(is_map_expr_start(p));
+
+    // `{` alone would open a block, so maps take a `%` first. It can't start
+    // an expression otherwise.
+    let marker = p.start();
+    p.bump(T![%]);
+    parse_tt(p,
+             MAP_EXPR,
+             T!['{'],
+             Some(T![,]),
+             T!['}'],
+             LIST_RECOVERY,
+             parse_map_entry).undo_completion(p)
+                             .abandon(p);
+    marker.complete(p, MAP_EXPR)
+}
+
+fn is_map_expr_start(p: &mut Parser) -> bool { p.at(T![%]) }
+
+fn parse_map_entry(p: &mut Parser)
+{
+    let marker = p.start();
+    parse_expr(p);
+    // a missing `:` still leaves the value, if there is one.
+    if p.expect(T![:]) || !(p.at(T![,]) || p.at(T!['}'])) {
+        parse_expr(p);
+    }
+    marker.complete(p, MAP_ENTRY);
+}
+
 fn parse_primary(p: &mut Parser) -> CompletedMarker
 {
+    // test err garbage_expression
+    // y = @ # 3
+    // z = 4
     p.skip_newlines();
 
+    if is_fn_def_start(p) {
+        return parse_fn_def(p)
+    }
+    if is_lambda_expr_start(p) {
+        return parse_lambda_expr(p)
+    }
+
     let mk = p.start();
 
     if is_array_expr_start(p) {
@@ -490,6 +1150,9 @@ fn parse_primary(p: &mut Parser) -> CompletedMarker
     else if is_tuple_expr_start(p) {
         parse_tuple_expr(p);
     }
+    else if is_map_expr_start(p) {
+        parse_map_expr(p);
+    }
     else if is_conditional_start(p) {
         // test if_condition_in_expr
         // a = if b {} else {}
@@ -507,70 +1170,255 @@ fn parse_primary(p: &mut Parser) -> CompletedMarker
         // }
         parse_conditional(p);
     }
+    else if is_match_expr_start(p) {
+        parse_match_expr(p);
+    }
     else if is_expr_block_start(p) {
         parse_expr_block(p);
     }
-    else if p.at_any([T![int_number],
-                        // T![float_number],
-                        T![ident]])
-               .is_some()
-    {
-        p.bump_any();
+    else if p.at(T![::]) || (p.at(T![ident]) && p.nth_at(1, T![::])) {
+        // test path_expr
+        // os = Os::Linux
+        // f(::Os::Mac, a.b)
+        parse_path(p);
     }
-    else if is_string_lit(p) {
-        parse_string(p);
+    else if p.at(T![ident]) {
+        // test unicode_idents
+        // größe = naïve_π + _x1 + 变量
+        p.bump(T![ident]);
+    }
+    else if p.at(T![this]) {
+        // test this_expr
+        // x = this.a
+        // this.b = f(this)
+        p.bump(T![this]);
+    }
+    else if is_literal_start(p) {
+        parse_literal(p);
+    }
+    else if is_interpolated_str_start(p) {
+        parse_interpolated_str(p);
     }
     else {
-        p.unexpected();
+        let msg = format!("expected an expression, got {:?}",
+                          p.current());
+        p.err_recover(p.err_here(ErrorCode::ExpectedExpr, msg),
+                      EXPR_RECOVERY);
     }
 
     mk.complete(p, PRIMARY_EXPR)
 }
 
+fn is_literal_start(p: &mut Parser) -> bool
+{
+    is_string_lit(p)
+    || p.at_any([T![int_number],
+                 T![float_number],
+                 T![char],
+                 T![true],
+                 T![false]])
+        .is_some()
+}
+
+fn parse_literal(p: &mut Parser) -> CompletedMarker
+{
+    // test number_literals
+    // x = 1 + 0x1f + 1.5 + 2e10 + 3.0e-2
+
+    // test char_literal
+    // x = c'a'
+    // y = c'\n'
+
+    // test string_literals
+    // x = 'a' + "b" + '''
+    // c
+    // '''
+
+    // test raw_string_literals
+    // x = r'C:\path\' + r"it's" + r#"say "hi""#
+
+    // test string_escapes
+    // x = 'tab\tquote\'' + "\u{1F600}\\"
+
+    // test err invalid_string_escapes
+    // x = 'C:\path' + "\u{D800}" + '\u41'
+
+    // test char_escapes
+    // x = [c'\r', c'\0', c'\"', c'\$', c'\'', c'\\', c'\u{1F600}']
+
+    // test err invalid_char_escapes
+    // x = [c'\q', c'\u{D800}', c'\u{}', c'\u']
+    assert!(is_literal_start(p));
+
+    let marker = p.start();
+    if is_string_lit(p) {
+        parse_string(p);
+    }
+    else {
+        let inner = p.start();
+        let kind = match p.current() {
+            T![int_number] | T![float_number] => NUMBER_LIT,
+            T![char] => CHAR_LIT,
+            T![true] | T![false] => BOOLEAN_LIT,
+            _ => unreachable!(),
+        };
+        p.bump_any();
+        inner.complete(p, kind);
+    }
+
+    marker.complete(p, LIT_VAL)
+}
+
 fn is_string_lit(p: &mut Parser) -> bool
 {
-    p.at_any([T![str], T![multiline_str]]).is_some()
+    p.at_any([T![str], T![multiline_str], T![raw_str]])
+     .is_some()
 }
 
-fn parse_string(p: &mut Parser)
+fn parse_string(p: &mut Parser) -> CompletedMarker
 {
     assert!(is_string_lit(p));
+    let marker = p.start();
     p.bump_any();
+    marker.complete(p, STRING_LIT)
+}
+
+fn is_interpolated_str_start(p: &mut Parser) -> bool { p.at(T![str_start]) }
+
+fn parse_interpolated_str(p: &mut Parser) -> CompletedMarker
+{
+    // test interpolated_string
+    // cmd = 'cc ${flags} -o ${out}.o "${src}"'
+
+    // test nested_interpolated_string
+    // x = "${'-I${dir}' + {a}}"
+
+    // test err empty_interpolation
+    // x = 'a${}b'
+    assert!(is_interpolated_str_start(p));
+
+    let marker = p.start();
+    p.bump(T![str_start]);
+    loop {
+        p.skip_newlines();
+        if p.at_any([T![str_middle], T![str_end]]).is_some() {
+            p.error(p.err_here(ErrorCode::ExpectedExpr,
+                               "expected an expression in the interpolation"));
+        }
+        else {
+            parse_expr(p);
+            p.skip_newlines();
+        }
+
+        if !p.eat(T![str_middle]) {
+            p.expect(T![str_end]);
+            break
+        }
+    }
+
+    marker.complete(p, INTERPOLATED_STR)
 }
 
+/// Parses `start_tok`, then items with `f` up to `end_tok`. The list gives
+/// up at a token in `recovery`, which belongs to whatever is around it.
+/// When `f` can't start an item it must report why, the tokens up to the
+/// next separator are then skipped.
 fn parse_tt(p: &mut Parser,
             outer_kind: SyntaxKind,
             start_tok: SyntaxKind,
             separator: Option<SyntaxKind>,
             end_tok: SyntaxKind,
+            recovery: TokenSet,
             mut f: impl FnMut(&mut Parser))
             -> CompletedMarker
 {
     let marker = p.start();
-    p.bump(start_tok);
+    let start_range = p.current_range();
+    if !p.expect(start_tok) {
+        return marker.complete(p, outer_kind)
+    }
+
+    // test err unclosed_array_in_call_args
+    // t = executable('app', flags = [)
+    // let y = 3
+
+    // test err unclosed_param_list
+    // fn f(a, b {
+    //     a + b
+    // }
+
+    // test err missing_separator_in_list
+    // x = ['a.c' 'b.c', 'c.c']
+    let recovery = recovery.union(TokenSet::new(&[end_tok]));
+    let item_recovery = recovery.union(separator.map_or(TokenSet::EMPTY,
+                                                        |it| {
+                                                            TokenSet::new(&[it])
+                                                        }));
 
     p.skip_newlines();
-    while !p.at_any([EOF, end_tok]).is_some() {
+    while !p.at(EOF) && !p.at_ts(recovery) {
+        let pos = p.pos;
         f(p);
+        if p.pos == pos {
+            p.recover(item_recovery);
+        }
 
         if let Some(separator) = separator {
+            // without a separator, a statement on the next line means the
+            // list was never closed.
+            if is_stmt_on_next_line(p) {
+                break
+            }
             p.skip_newlines();
             if p.eat(separator) {
             }
-            else if !p.at(end_tok) {
-                p.error(format!("expected {:?} or {:?}, got {:?}",
-                                separator,
-                                end_tok,
-                                p.current()));
+            else if !p.at(EOF) && !p.at_ts(recovery) {
+                let msg = format!("expected {:?} or {:?}, got {:?}",
+                                  separator,
+                                  end_tok,
+                                  p.current());
+                let err = p.err_here(ErrorCode::ExpectedToken, msg)
+                           .with_expected(&[separator, end_tok]);
+                p.error(err);
             }
         }
         p.skip_newlines();
+        if p.pos == pos {
+            break
+        }
     }
 
-    p.expect(end_tok);
+    if !p.eat(end_tok) {
+        let msg = format!("expected {:?}, got {:?}",
+                          end_tok,
+                          p.current());
+        let err = p.err_here(ErrorCode::UnclosedDelimiter, msg)
+                   .with_expected(&[end_tok])
+                   .with_label(start_range,
+                               format!("{:?} opened here", start_tok));
+        p.error(err);
+    }
     marker.complete(p, outer_kind)
 }
 
+/// Whether the next line starts with an assignment, an attribute or a
+/// declaration, which no list item does.
+fn is_stmt_on_next_line(p: &Parser) -> bool
+{
+    if !p.at(T![newline]) {
+        return false
+    }
+
+    let n = p.next_not_newline().offset;
+    let is_assignment = p.nth_at(n, T![ident])
+                        && p.nth_at(n + 1, T![=])
+                        && !p.nth_at(n + 1, T![==]);
+    is_assignment
+    || p.nth_at(n, T![@])
+    || p.nth_at(n, T![fn])
+    || STMT_KW.contains(p.nth(n))
+}
+
 fn parse_precedence_1_expr(p: &mut Parser) -> CompletedMarker
 {
     p.skip_newlines();
@@ -601,7 +1449,8 @@ fn parse_precedence_1_expr(p: &mut Parser) -> CompletedMarker
                 }
             }
         }
-        else if p.bump_to_if_next_non_newline_is(T![.]) {
+        // the start of a range isn't a member access
+        else if !p.at(T![..]) && p.bump_to_if_next_non_newline_is(T![.]) {
             let new_marker = marker.precede(p);
             marker = parse_member_expr(p, new_marker);
 
@@ -634,11 +1483,24 @@ fn parse_f_call(p: &mut Parser, marker: Marker) -> CompletedMarker
     // x = f
     // (1, 2, a = b)
 
+    // test err unclosed_call_args_before_statement
+    // executable('app', srcs
+    // lib = library('x')
+    // x = 3
+
+    // test named_args_on_many_lines
+    // app = executable(
+    //     'app',
+    //     srcs = srcs,
+    //     deps = [lib]
+    // )
+
     parse_tt(p,
              FN_CALL_ARGS,
              T!['('],
              Some(T![,]),
              T![')'],
+             LIST_RECOVERY,
              parse_farg);
     marker.complete(p, FN_CALL_EXPR)
 }
@@ -647,6 +1509,21 @@ fn parse_farg(p: &mut Parser)
 {
     p.skip_newlines();
 
+    // a contextual keyword is a name as the key of a named argument.
+    if p.current().is_contextual_keyword()
+       && p.nth_at(1, T![=])
+       && !p.nth_at(1, T![==])
+    {
+        let marker = p.start();
+        let key = p.start();
+        p.bump_remap(IDENT);
+        key.complete(p, PRIMARY_EXPR);
+        p.bump(T![=]);
+        parse_expr(p);
+        marker.complete(p, BIN_EXPR);
+        return
+    }
+
     parse_expr(p)
 }
 
@@ -662,8 +1539,21 @@ fn parse_index_expr(p: &mut Parser, marker: Marker) -> CompletedMarker
 
 fn parse_member_expr(p: &mut Parser, marker: Marker) -> CompletedMarker
 {
+    // test contextual_keywords_as_names
+    // x = target.in + opts.as.len()
+    // lib = library(as = 'static', in = srcs, this = 1)
+    // for src in srcs {}
+
+    // test err contextual_keywords_elsewhere
+    // let in = 1
+    // f(as == 1)
     p.bump(T![.]);
-    p.expect(T![ident]);
+    if p.current().is_contextual_keyword() {
+        p.bump_remap(IDENT);
+    }
+    else {
+        p.expect(T![ident]);
+    }
 
     marker.complete(p, MEMBER_ACCESS_EXPR)
 }
@@ -675,6 +1565,7 @@ fn parse_method_call(p: &mut Parser, marker: Marker) -> CompletedMarker
              T!['('],
              Some(T![,]),
              T![')'],
+             LIST_RECOVERY,
              parse_farg);
 
     marker.complete(p, METHOD_CALL_EXPR)
@@ -686,7 +1577,9 @@ fn parse_precedence_2_expr(p: &mut Parser) -> CompletedMarker
     if let Some(kind) = p.at_any([T![+], T![-], T![!]]) {
         let marker = p.start();
         p.bump(kind);
-        parse_precedence_2_expr(p);
+        p.nested(EXPR_BRACKETS, |p| {
+             parse_precedence_2_expr(p);
+         });
         marker.complete(p, PREFIX_UNARY_EXPR)
     }
     else {
@@ -723,7 +1616,21 @@ fn parse_infix_binop<I>(p: &mut Parser,
     // * 6
     // % 78
 
-    while let Some(kind) = p.at_any(ops) {
+    // test map_expr_after_newline
+    // x = {
+    //     a
+    //     %{ 1: 2 }
+    // }
+    // y = b
+    // % { 3: 4 }
+
+    // like `|>`, an operator can go on the next line, but a `%{` there
+    // starts a map: the value of a block often ends with one.
+    while let Some(kind) = p.next_non_newline_at_any(ops) {
+        if kind == T![%] && p.at(T![newline]) && is_map_on_next_line(p) {
+            break
+        }
+        p.skip_newlines();
         let prec = completed.precede(p);
         p.bump(kind);
         lower(p);
@@ -734,6 +1641,12 @@ fn parse_infix_binop<I>(p: &mut Parser,
     completed
 }
 
+fn is_map_on_next_line(p: &mut Parser) -> bool
+{
+    let tk = p.next_not_newline();
+    p.nth_at(tk.offset + 1, T!['{'])
+}
+
 fn parse_precedence_3_expr(p: &mut Parser) -> CompletedMarker
 {
     parse_infix_binop(p,
@@ -773,6 +1686,89 @@ fn parse_precedence_8_expr(p: &mut Parser) -> CompletedMarker
 }
 
 fn parse_precedence_9_expr(p: &mut Parser) -> CompletedMarker
+{
+    // test range_expr
+    // for i in 0..n {}
+    // r = a + 1..=b * 2
+
+    // test err chained_range_expr
+    // r = 1..2..3
+    p.skip_newlines();
+    let completed = parse_precedence_8_expr(p);
+
+    // `..=` first, `..` is a prefix of it
+    match p.at_any([T![..=], T![..]]) {
+        Some(kind) => {
+            let marker = completed.precede(p);
+            p.bump(kind);
+            parse_precedence_8_expr(p);
+            marker.complete(p, RANGE_EXPR)
+        }
+        None => completed,
+    }
+}
+
+fn parse_precedence_10_expr(p: &mut Parser) -> CompletedMarker
+{
+    // test pipe_expr
+    // srcs = glob('*.c') |> exclude('main.c') |> sort()
+    // objs = srcs
+    //     |> map(|it| it + '.o')
+    //
+    //     |> sort
+
+    // test err pipe_without_rhs
+    // x = a |>
+    p.skip_newlines();
+    let mut completed = parse_precedence_9_expr(p);
+
+    // like `.method()`, a `|>` can go on the next line
+    while p.bump_to_if_next_non_newline_is(T![|>]) {
+        let marker = completed.precede(p);
+        p.bump(T![|>]);
+        parse_precedence_9_expr(p);
+        completed = marker.complete(p, PIPE_EXPR);
+    }
+    completed
+}
+
+fn parse_precedence_11_expr(p: &mut Parser) -> CompletedMarker
+{
+    // test ternary_expr
+    // flags = debug ? ['-g'] : ['-O2']
+    // x = a ? b : c ? d : e
+    // y = a || b ? 1..2 : 3..4
+
+    // test err ternary_without_else
+    // x = a ? b
+    // y = 1
+    p.skip_newlines();
+    let mut completed = parse_precedence_10_expr(p);
+
+    // `a ? b : c ? d : e` nests to the right. The else branches go in a loop
+    // rather than deeper, each ternary completes at the end of the chain.
+    let mut markers = vec![];
+    while p.at(T![?]) {
+        let marker = completed.precede(p);
+        p.bump(T![?]);
+        p.nested(TERNARY_BRACKETS, |p| {
+             parse_precedence_11_expr(p);
+         });
+        if !p.expect(T![:]) {
+            completed = marker.complete(p, TERNARY_EXPR);
+            break
+        }
+        markers.push(marker);
+        p.skip_newlines();
+        completed = parse_precedence_10_expr(p);
+    }
+    for marker in markers.into_iter().rev() {
+        completed = marker.complete(p, TERNARY_EXPR);
+    }
+    completed
+}
+
+fn parse_precedence_12_expr(p: &mut Parser) -> CompletedMarker
 {
     parse_infix_binop(p,
                       [T![=],
@@ -786,7 +1782,7 @@ fn parse_precedence_9_expr(p: &mut Parser) -> CompletedMarker
                        T![^=],
                        T![&&=],
                        T![||=]],
-                      parse_precedence_8_expr)
+                      parse_precedence_11_expr)
 }
 
 fn parse_expr_block(p: &mut Parser) -> CompletedMarker
@@ -797,7 +1793,8 @@ fn parse_expr_block(p: &mut Parser) -> CompletedMarker
              T!['{'],
              None,
              T!['}'],
-             parse_statement)
+             TokenSet::EMPTY,
+             |p| p.nested(EXPR_BRACKETS, parse_statement))
 }
 
 fn is_expr_block_start(p: &mut Parser) -> bool { p.at(T!['{']) }
@@ -809,11 +1806,24 @@ fn parse_declaration(p: &mut Parser) -> CompletedMarker
 
     // test var_declaration_with_proper_expr_as_value
     // let x = 1
+
+    // test underscore_names
+    // let _ = f()
+    // _ = 2
+    // fn g(_, _x) { _ }
+    // xs.map(|_| 1)
+    // for _ in xs {}
     p.skip_newlines();
     let mk = p.start();
     p.expect(T![let]);
     p.expect(T![ident]);
 
+    // test typed_declaration
+    // let flags: list<str> = []
+    if p.eat(T![:]) {
+        parse_type_ref(p);
+    }
+
     p.expect(T![=]);
 
     parse_expr(p);
@@ -823,6 +1833,272 @@ fn parse_declaration(p: &mut Parser) -> CompletedMarker
     mk.complete(p, DECLARATION_STMT)
 }
 
+fn is_fn_def_start(p: &mut Parser) -> bool { p.at(T![fn]) }
+
+fn parse_fn_def(p: &mut Parser) -> CompletedMarker
+{
+    // test fn_def
+    // fn compile(src, out = 'a.out', flags = []) {
+    //     cc(src, o = out)
+    // }
+
+    // test fn_def_in_expr
+    // add = fn add(a, b) { a + b }
+
+    // test fn_def_params_on_many_lines
+    // fn f(
+    //     a,
+    //     b = 1,
+    // )
+    // {
+    // }
+
+    // test err fn_def_without_name
+    // fn (a) {}
+    p.skip_newlines();
+    assert!(is_fn_def_start(p));
+
+    let marker = p.start();
+    p.bump(T![fn]);
+    p.expect(T![ident]);
+    if p.at(T!['(']) {
+        parse_param_list(p);
+    }
+    else {
+        p.expect_failed(T!['(']);
+    }
+    if p.at(T![->]) {
+        parse_ret_type(p);
+    }
+    parse_expr_block(p);
+
+    marker.complete(p, FN_DEF)
+}
+
+fn parse_param_list(p: &mut Parser) -> CompletedMarker
+{
+    // test err param_without_name
+    // fn f(a, 1) {}
+    parse_tt(p,
+             PARAM_LIST,
+             T!['('],
+             Some(T![,]),
+             T![')'],
+             LIST_RECOVERY.union(TokenSet::new(&[T!['{']])),
+             |p| parse_param(p, true))
+}
+
+/// Parses a parameter, with a default value only if `with_default`.
+fn parse_param(p: &mut Parser, with_default: bool)
+{
+    if !p.at(T![ident]) {
+        p.expect_failed(T![ident]);
+        return
+    }
+    let marker = p.start();
+    p.bump(T![ident]);
+    if p.eat(T![:]) {
+        parse_type_ref(p);
+    }
+    if with_default && p.eat(T![=]) {
+        parse_expr(p);
+    }
+    marker.complete(p, PARAM);
+}
+
+fn is_lambda_expr_start(p: &mut Parser) -> bool { p.at(T![|]) }
+
+fn parse_lambda_expr(p: &mut Parser) -> CompletedMarker
+{
+    // test lambda_expr
+    // c_files = sources.filter(|f| f.ends_with('.c'))
+    // add = |a, b| a + b
+    // noop = || {}
+    // double = |n: int| {
+    //     n * 2
+    // }
+
+    // test err lambda_param_with_default
+    // f = |a = 1| a
+    p.skip_newlines();
+    assert!(is_lambda_expr_start(p));
+
+    // `||` is two `|` tokens, so it reads as an empty parameter list. There
+    // are no defaults, the default's expression would take the closing `|`
+    // for an operator.
+    let marker = p.start();
+    parse_tt(p,
+             PARAM_LIST,
+             T![|],
+             Some(T![,]),
+             T![|],
+             LIST_RECOVERY,
+             |p| parse_param(p, false));
+    parse_expr(p);
+    marker.complete(p, LAMBDA_EXPR)
+}
+
+fn parse_struct_def(p: &mut Parser) -> CompletedMarker
+{
+    // test struct_def
+    // struct Target { name: str, sources: list }
+
+    // test struct_def_on_many_lines
+    // struct Options
+    // {
+    //     debug: bool,
+    //     opt_level: int,
+    // }
+
+    // test err struct_field_without_type
+    // struct S { a, b: int }
+    let marker = p.start();
+    p.bump(T![struct]);
+    p.expect(T![ident]);
+    p.skip_newlines();
+    if p.at(T!['{']) {
+        parse_tt(p,
+                 FIELD_LIST,
+                 T!['{'],
+                 Some(T![,]),
+                 T!['}'],
+                 LIST_RECOVERY,
+                 parse_field_def);
+    }
+    else {
+        p.expect_failed(T!['{']);
+    }
+    p.require_newline();
+
+    marker.complete(p, STRUCT_DEF)
+}
+
+fn parse_field_def(p: &mut Parser)
+{
+    if !p.at(T![ident]) {
+        p.expect_failed(T![ident]);
+        return
+    }
+    let marker = p.start();
+    p.bump(T![ident]);
+    if p.expect(T![:]) {
+        parse_type_ref(p);
+    }
+    marker.complete(p, FIELD_DEF);
+}
+
+fn parse_enum_def(p: &mut Parser) -> CompletedMarker
+{
+    // test enum_def
+    // enum BuildType { Debug, Release, }
+
+    // test enum_def_on_many_lines
+    // enum Os
+    // {
+    //     Linux,
+    //     Windows
+    // }
+    let marker = p.start();
+    p.bump(T![enum]);
+    p.expect(T![ident]);
+    p.skip_newlines();
+    if p.at(T!['{']) {
+        parse_tt(p,
+                 VARIANT_LIST,
+                 T!['{'],
+                 Some(T![,]),
+                 T!['}'],
+                 LIST_RECOVERY,
+                 parse_variant);
+    }
+    else {
+        p.expect_failed(T!['{']);
+    }
+    p.require_newline();
+
+    marker.complete(p, ENUM_DEF)
+}
+
+fn parse_variant(p: &mut Parser)
+{
+    if !p.at(T![ident]) {
+        p.expect_failed(T![ident]);
+        return
+    }
+    let marker = p.start();
+    p.bump(T![ident]);
+    marker.complete(p, VARIANT);
+}
+
+fn parse_ret_type(p: &mut Parser) -> CompletedMarker
+{
+    // test fn_def_with_types
+    // fn link(objs: list<path>, out: str = 'a.out') -> path {}
+
+    // test fn_def_never_returns
+    // fn fail(msg: str) -> ! {}
+    let marker = p.start();
+    p.bump(T![->]);
+    parse_type_ref(p);
+    marker.complete(p, RET_TYPE)
+}
+
+fn parse_type_ref(p: &mut Parser) -> CompletedMarker
+{
+    // test type_refs
+    // let a: int = 1
+    // let b: map<str, list<int>> = x
+    // let c: ::std::path = x
+    // let d: _ = x
+
+    // test err type_ref_missing
+    // let a: = 1
+    let marker = p.start();
+    if p.at(T![!]) || p.at(T![_]) {
+        p.bump_any();
+    }
+    else if p.at(T![ident]) || p.at(T![::]) {
+        parse_path(p);
+        if p.at(T![<]) {
+            parse_tt(p,
+                     GENERIC_ARG_LIST,
+                     T![<],
+                     Some(T![,]),
+                     T![>],
+                     LIST_RECOVERY,
+                     |p| {
+                         p.nested(TYPE_BRACKETS, |p| {
+                              parse_type_ref(p);
+                          })
+                     });
+        }
+    }
+    else {
+        let msg = format!("expected a type, got {:?}", p.current());
+        p.error(p.err_here(ErrorCode::ExpectedType, msg));
+    }
+    marker.complete(p, TYPE_REF)
+}
+
+fn parse_path(p: &mut Parser) -> CompletedMarker
+{
+    let marker = p.start();
+    p.eat(T![::]);
+    p.expect(T![ident]);
+    parse_path_frags(p);
+    marker.complete(p, PATH)
+}
+
+fn parse_path_frags(p: &mut Parser)
+{
+    while p.at(T![::]) {
+        let frag = p.start();
+        p.bump(T![::]);
+        p.expect(T![ident]);
+        frag.complete(p, PATH_FRAG);
+    }
+}
+
 fn is_conditional_start(p: &mut Parser) -> bool { p.at(T![if]) }
 
 fn parse_conditional(p: &mut Parser) -> CompletedMarker
@@ -862,12 +2138,15 @@ fn parse_conditional(p: &mut Parser) -> CompletedMarker
     //      1
     // {}
     while p.bump_to_if_next_non_newline_is(T![else]) {
+        let branch = p.start();
         p.bump(T![else]);
         if p.bump_to_if_next_non_newline_is(T![if]) {
-            parse_conditional_branch(p);
+            parse_cond_and_block(p);
+            branch.complete(p, ELSE_IF_BRANCH);
         }
         else {
             parse_expr_block(p);
+            branch.complete(p, ELSE_BRANCH);
             break
         }
     }
@@ -879,6 +2158,14 @@ fn parse_conditional_branch(p: &mut Parser)
 {
     p.skip_newlines();
 
+    let marker = p.start();
+    parse_cond_and_block(p);
+    marker.complete(p, IF_BRANCH);
+}
+
+/// Parses the `if cond {}` of a branch.
+fn parse_cond_and_block(p: &mut Parser)
+{
     p.bump(T![if]);
 
     parse_expr(p);
@@ -886,6 +2173,51 @@ fn parse_conditional_branch(p: &mut Parser)
     parse_expr_block(p);
 }
 
+fn is_match_expr_start(p: &mut Parser) -> bool { p.at(T![match]) }
+
+fn parse_match_expr(p: &mut Parser) -> CompletedMarker
+{
+    // test match_expr
+    // flags = match os {
+    //     'linux' => ['-pthread'],
+    //     Os::Windows => [],
+    //     (cc, true) => cc.flags(),
+    //     _ => [],
+    // }
+
+    // test match_expr_on_one_line
+    // n = match x { 1 => 'one', other => 'many' }
+
+    // test err match_arm_without_arrow
+    // x = match a { 1 2, _ => 3 }
+    p.skip_newlines();
+    assert!(is_match_expr_start(p));
+
+    let marker = p.start();
+    p.bump(T![match]);
+    parse_expr(p);
+    p.skip_newlines();
+    parse_tt(p,
+             MATCH_ARM_LIST,
+             T!['{'],
+             Some(T![,]),
+             T!['}'],
+             LIST_RECOVERY,
+             parse_match_arm);
+    marker.complete(p, MATCH_EXPR)
+}
+
+fn parse_match_arm(p: &mut Parser)
+{
+    let marker = p.start();
+    parse_pat(p);
+    // a missing `=>` still leaves the expression, if there is one.
+    if p.expect(T![=>]) || !(p.at(T![,]) || p.at(T!['}'])) {
+        parse_expr(p);
+    }
+    marker.complete(p, MATCH_ARM);
+}
+
 fn parse_for(p: &mut Parser) -> CompletedMarker
 {
     p.skip_newlines();
@@ -893,10 +2225,24 @@ fn parse_for(p: &mut Parser) -> CompletedMarker
     // test for_basic
     // for a in b {}
 
+    // test for_tuple_pat
+    // for (k, (_, v)) in map {}
+
+    // test err for_without_pat
+    // for in b {}
+
     let marker = p.start();
 
     p.bump(T![for]);
-    parse_for_in_expr(p);
+    if p.at(T![in]) {
+        p.error(p.err_here(ErrorCode::ExpectedPat,
+                           "expected a pattern, got IN_KW"));
+    }
+    else {
+        parse_pat(p);
+    }
+    p.expect(T![in]);
+    parse_expr(p);
     parse_expr_block(p);
 
     p.require_newline();
@@ -904,13 +2250,50 @@ fn parse_for(p: &mut Parser) -> CompletedMarker
     marker.complete(p, FOR_STMT)
 }
 
-fn parse_for_in_expr(p: &mut Parser) -> CompletedMarker
+fn parse_pat(p: &mut Parser)
 {
-    let marker = p.start();
-    parse_expr(p);
-    p.expect(T![in]);
-    parse_expr(p);
-    marker.complete(p, FOR_IN_EXPR)
+    if p.at(T!['(']) {
+        parse_tt(p,
+                 TUPLE_PAT,
+                 T!['('],
+                 Some(T![,]),
+                 T![')'],
+                 LIST_RECOVERY,
+                 |p| p.nested(EXPR_BRACKETS, parse_pat));
+        return
+    }
+    if is_literal_start(p) {
+        parse_literal(p).precede(p).complete(p, LIT_PAT);
+        return
+    }
+    if p.at(T![::]) {
+        parse_path(p).precede(p).complete(p, PATH_PAT);
+        return
+    }
+    if p.at(T![_]) {
+        let marker = p.start();
+        p.bump(T![_]);
+        marker.complete(p, WILDCARD_PAT);
+        return
+    }
+    if p.at(T![ident]) {
+        // `Os::Linux` is an enum variant, a lone `os` binds a name.
+        let marker = p.start();
+        p.bump(T![ident]);
+        if p.at(T![::]) {
+            parse_path_frags(p);
+            marker.complete(p, PATH).precede(p).complete(p, PATH_PAT);
+        }
+        else {
+            marker.complete(p, IDENT_PAT);
+        }
+        return
+    }
+
+    let msg = format!("expected a pattern, got {:?}",
+                      p.current());
+    p.err_recover(p.err_here(ErrorCode::ExpectedPat, msg),
+                  PAT_RECOVERY);
 }
 
 fn parse_while(p: &mut Parser) -> CompletedMarker
@@ -928,7 +2311,7 @@ fn parse_while(p: &mut Parser) -> CompletedMarker
 
     p.require_newline();
 
-    marker.complete(p, FOR_STMT)
+    marker.complete(p, WHILE_STMT)
 }
 
 fn is_control_stmt(p: &mut Parser) -> bool
@@ -989,25 +2372,43 @@ fn parse_control_stmt(p: &mut Parser) -> CompletedMarker
 
 pub fn print_ast(node: &SyntaxNode, ident: usize)
 {
-    println!("{:ident$}{:?}@{:?}..{:?}",
+    let mut buf = String::new();
+    write_ast(&mut buf, node, ident);
+    print!("{}", buf);
+}
+
+/// The tree under `node`, one node or token per line, as [`print_ast`]
+/// prints it.
+pub fn debug_ast(node: &SyntaxNode) -> String
+{
+    let mut buf = String::new();
+    write_ast(&mut buf, node, 0);
+    buf
+}
+
+fn write_ast(buf: &mut String, node: &SyntaxNode, ident: usize)
+{
+    writeln!(buf,
+             "{:ident$}{:?}@{:?}..{:?}",
              "",
              node.kind(),
              node.text_range().start(),
              node.text_range().end(),
-             ident = ident * 2);
+             ident = ident * 2).unwrap();
     for child in node.children_with_tokens() {
         match child {
             NodeOrToken::Node(n) => {
-                print_ast(&n, ident + 1);
+                write_ast(buf, &n, ident + 1);
             }
             NodeOrToken::Token(t) => {
-                println!("{:ident$}{:?}@{:?}..{:?} {:?}",
+                writeln!(buf,
+                         "{:ident$}{:?}@{:?}..{:?} {:?}",
                          "",
                          t.kind(),
                          t.text_range().start(),
                          t.text_range().end(),
                          t.text(),
-                         ident = (ident + 1) * 2);
+                         ident = (ident + 1) * 2).unwrap();
             }
         }
     }
@@ -1,8 +1,122 @@
+use std::fmt;
+
+use lfr_syntax::SyntaxKind;
+use lfr_syntax::rowan::TextRange;
+
 /// A parse error.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ParseError(pub Box<String>);
+pub struct ParseError
+{
+    pub code:     ErrorCode,
+    pub message:  String,
+    /// The offending token, or the tokens that were skipped because of it.
+    pub range:    TextRange,
+    /// The token kinds that would have been accepted instead, if the parser
+    /// was after specific ones.
+    pub expected: Vec<SyntaxKind>,
+    /// Other places worth pointing at, like the opening bracket of a list
+    /// that was never closed.
+    pub labels:   Vec<Label>,
+}
+
+/// A secondary location of a [`ParseError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label
+{
+    pub range:   TextRange,
+    pub message: String,
+}
+
+/// What kind of error a [`ParseError`] is. The codes are stable, tools may
+/// match on them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode
+{
+    /// `E0001`: a specific token is missing, see [`ParseError::expected`].
+    ExpectedToken,
+    /// `E0002`: a token that can't start a statement.
+    UnexpectedToken,
+    /// `E0003`: an expression is missing.
+    ExpectedExpr,
+    /// `E0004`: a pattern is missing.
+    ExpectedPat,
+    /// `E0005`: a type is missing.
+    ExpectedType,
+    /// `E0006`: a bracketed list is never closed.
+    UnclosedDelimiter,
+    /// `E0007`: a string literal has an invalid escape.
+    InvalidEscape,
+    /// `E0008`: tokens are left after a fragment, see
+    /// [`parse_expr_fragment`](crate::parser::parse_expr_fragment).
+    TrailingTokens,
+    /// `E0009`: expressions, patterns or types nest too deep, see
+    /// [`NESTING_LIMIT`](crate::parser::NESTING_LIMIT).
+    NestingTooDeep,
+}
+
+impl ErrorCode
+{
+    pub fn as_str(self) -> &'static str
+    {
+        match self {
+            Self::ExpectedToken => "E0001",
+            Self::UnexpectedToken => "E0002",
+            Self::ExpectedExpr => "E0003",
+            Self::ExpectedPat => "E0004",
+            Self::ExpectedType => "E0005",
+            Self::UnclosedDelimiter => "E0006",
+            Self::InvalidEscape => "E0007",
+            Self::TrailingTokens => "E0008",
+            Self::NestingTooDeep => "E0009",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        f.write_str(self.as_str())
+    }
+}
+
+impl ParseError
+{
+    pub fn new(code: ErrorCode,
+               range: TextRange,
+               message: impl Into<String>)
+               -> Self
+    {
+        Self { code,
+               message: message.into(),
+               range,
+               expected: vec![],
+               labels: vec![] }
+    }
+
+    pub fn with_expected(mut self, expected: &[SyntaxKind]) -> Self
+    {
+        self.expected.extend_from_slice(expected);
+        self
+    }
+
+    pub fn with_label(mut self,
+                      range: TextRange,
+                      message: impl Into<String>)
+                      -> Self
+    {
+        self.labels.push(Label { range,
+                                 message: message.into() });
+        self
+    }
+}
 
-impl<T> From<T> for ParseError where T: Into<String>
+impl fmt::Display for ParseError
 {
-    fn from(s: T) -> Self { Self(Box::new(s.into())) }
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f,
+               "error[{}]: {}",
+               self.code, self.message)
+    }
 }
@@ -0,0 +1,298 @@
+//! Reparsing after an edit, reusing as much of the old tree as possible.
+//!
+//! An edit inside a single whitespace, comment or identifier token relexes
+//! just that token. Otherwise the smallest `BLOCK`, `TUPLE_EXPR` or
+//! `ARR_EXPR` around the edit is parsed again on its own and spliced into
+//! the old tree. When neither applies, the whole text is parsed again.
+
+use lfr_syntax::SyntaxKind::{
+    self,
+    *,
+};
+use lfr_syntax::rowan::{
+    GreenNode,
+    GreenToken,
+    NodeOrToken,
+    TextRange,
+    TextSize,
+};
+use lfr_syntax::{
+    SyntaxNode,
+    SyntaxToken,
+};
+
+use super::error::{
+    ErrorCode,
+    ParseError,
+};
+use super::{
+    parse,
+    parse_node,
+};
+use crate::lexer::Lexer;
+
+/// A replacement of the text in `delete` by `insert`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit
+{
+    pub delete: TextRange,
+    pub insert: String,
+}
+
+impl TextEdit
+{
+    pub fn new(delete: TextRange, insert: impl Into<String>) -> Self
+    {
+        Self { delete,
+               insert: insert.into() }
+    }
+
+    /// The text `text` becomes after the edit.
+    pub fn apply(&self, text: &str) -> String
+    {
+        let mut text = text.to_owned();
+        text.replace_range(std::ops::Range::<usize>::from(self.delete),
+                           &self.insert);
+        text
+    }
+
+    /// The range `range` covers after the edit, provided the edit is within
+    /// it.
+    fn apply_to_range(&self, range: TextRange) -> TextRange
+    {
+        TextRange::new(range.start(),
+                       range.end() + TextSize::of(&self.insert)
+                       - self.delete.len())
+    }
+}
+
+/// Parses `new_text`, which `edit` made out of the text of `root`, reusing
+/// `root` where possible. `errors` are the errors `root` was parsed with.
+/// Gives the same tree and errors as [`parse`]`(new_text)`.
+pub fn reparse(root: &SyntaxNode,
+               errors: &[ParseError],
+               edit: &TextEdit,
+               new_text: &str)
+               -> (GreenNode, Vec<ParseError>)
+{
+    if let Some(reparsed) = incremental_reparse(root, errors, edit, new_text) {
+        return reparsed
+    }
+    parse(new_text)
+}
+
+/// [`reparse`], unless the edit can't be confined to a token or a balanced
+/// node.
+pub(crate) fn incremental_reparse(root: &SyntaxNode,
+                                  errors: &[ParseError],
+                                  edit: &TextEdit,
+                                  new_text: &str)
+                                  -> Option<(GreenNode, Vec<ParseError>)>
+{
+    debug_assert_eq!(TextSize::of(new_text),
+                     edit.apply_to_range(root.text_range()).end());
+
+    if let Some((token, new_token)) =
+        reparse_token(root, errors, edit, new_text)
+    {
+        let range = token.text_range();
+        let errors = merge_errors(errors, vec![], range, range, edit)?;
+        return Some((token.replace_with(new_token), errors))
+    }
+
+    let (node, new_node, new_errors) = reparse_block(root, edit, new_text)?;
+    // The opening delimiter stays, errors about it came from the parent.
+    let open = node.first_token()?.text_range();
+    let range = node.text_range();
+    let errors = merge_errors(errors,
+                              new_errors,
+                              TextRange::new(open.end(), range.end()),
+                              range,
+                              edit)?;
+    Some((node.replace_with(new_node), errors))
+}
+
+/// Relexes the whitespace, comment or identifier the edit is within, if it
+/// remains a single token of the same kind that doesn't merge with its
+/// neighbours, and no error points into it.
+fn reparse_token(root: &SyntaxNode,
+                 errors: &[ParseError],
+                 edit: &TextEdit,
+                 new_text: &str)
+                 -> Option<(SyntaxToken, GreenToken)>
+{
+    let token = root.covering_element(edit.delete).into_token()?;
+    if !matches!(token.kind(),
+                 WHITESPACE
+                 | COMMENT
+                 | BLOCK_COMMENT
+                 | DOC_COMMENT
+                 | BLOCK_DOC_COMMENT
+                 | IDENT)
+       || !token.text_range().contains_range(edit.delete)
+    {
+        return None
+    }
+    let points_into_token = |range: TextRange| {
+        range.intersect(token.text_range())
+             .is_some_and(|it| !it.is_empty())
+    };
+    if errors.iter().flat_map(error_ranges).any(points_into_token) {
+        return None
+    }
+
+    let range = edit.apply_to_range(token.text_range());
+    let text = &new_text[range];
+    // a contextual keyword lexes as an `IDENT`, but the parser may take it
+    // for a keyword.
+    if lex_single(text) != Some(token.kind())
+       || SyntaxKind::from_contextual_keyword(text).is_some()
+    {
+        return None
+    }
+    if let Some(next) = new_text[range.end().into()..].chars().next() {
+        if lex_single(&format!("{}{}", text, next)).is_some() {
+            return None
+        }
+    }
+    if let Some(prev) = new_text[..range.start().into()].chars().next_back() {
+        if lex_single(&format!("{}{}", prev, text)).is_some() {
+            return None
+        }
+    }
+
+    let new_token = GreenToken::new(token.kind().into(), text);
+    Some((token, new_token))
+}
+
+/// Parses the smallest `BLOCK`, `TUPLE_EXPR` or `ARR_EXPR` around the edit
+/// again, returning the old node, the new one and its errors.
+fn reparse_block(root: &SyntaxNode,
+                 edit: &TextEdit,
+                 new_text: &str)
+                 -> Option<(SyntaxNode, GreenNode, Vec<ParseError>)>
+{
+    let node = match root.covering_element(edit.delete) {
+        NodeOrToken::Node(node) => node,
+        NodeOrToken::Token(token) => token.parent()?,
+    };
+    let node =
+        node.ancestors()
+            .find(|it| matches!(it.kind(), BLOCK | TUPLE_EXPR | ARR_EXPR))?;
+
+    // each level of nesting is a node at least, so the node sits no deeper
+    // than it has ancestors.
+    let depth = u32::try_from(node.ancestors().count()).ok()?;
+    let range = edit.apply_to_range(node.text_range());
+    let (green, mut errors) = parse_node(&new_text[range], node.kind(), depth)?;
+    for err in &mut errors {
+        err.range += range.start();
+        for label in &mut err.labels {
+            label.range += range.start();
+        }
+    }
+    Some((node, green, errors))
+}
+
+/// The kind of the token `text` lexes into, if it's a single one.
+fn lex_single(text: &str) -> Option<SyntaxKind>
+{
+    let mut lexer = Lexer::new(text);
+    let (kind, _) = lexer.next()?;
+    lexer.next().is_none().then_some(kind)
+}
+
+fn error_ranges(err: &ParseError) -> impl Iterator<Item = TextRange> + '_
+{
+    std::iter::once(err.range).chain(err.labels.iter().map(|it| it.range))
+}
+
+/// Where an old error sits relative to the reparsed range.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Place
+{
+    Before,
+    Inside,
+    After,
+}
+
+/// The errors of the new tree: the old ones outside of `replaced`, shifted
+/// past the edit if they're after `node`, and the `new` ones in place of
+/// those inside. Gives up on an old error that straddles `replaced`, or
+/// that points both inside and outside of it.
+///
+/// [`parse`] reports the syntax errors in source order, then the invalid
+/// escapes in source order, so each kind is merged on its own.
+fn merge_errors(old: &[ParseError],
+                new: Vec<ParseError>,
+                replaced: TextRange,
+                node: TextRange,
+                edit: &TextEdit)
+                -> Option<Vec<ParseError>>
+{
+    let new_end = edit.apply_to_range(node).end();
+    let place = |range: TextRange| {
+        if range.end() <= replaced.start() {
+            Some(Place::Before)
+        }
+        else if range.start() >= replaced.end() {
+            Some(Place::After)
+        }
+        else if replaced.contains_range(range) {
+            Some(Place::Inside)
+        }
+        else {
+            None
+        }
+    };
+    let shift = |range: TextRange| {
+        if range.start() >= replaced.end() {
+            range - node.end() + new_end
+        }
+        else {
+            range
+        }
+    };
+
+    let mut before = (vec![], vec![]);
+    let mut after = (vec![], vec![]);
+    for err in old {
+        let places = error_ranges(err).map(place).collect::<Option<Vec<_>>>()?;
+        let inside = places[0] == Place::Inside;
+        if places.iter().any(|&it| (it == Place::Inside) != inside) {
+            return None
+        }
+        if inside {
+            continue
+        }
+
+        let mut err = err.clone();
+        err.range = shift(err.range);
+        for label in &mut err.labels {
+            label.range = shift(label.range);
+        }
+        let group = if places[0] == Place::Before {
+            &mut before
+        }
+        else {
+            &mut after
+        };
+        if err.code == ErrorCode::InvalidEscape {
+            group.1.push(err);
+        }
+        else {
+            group.0.push(err);
+        }
+    }
+
+    let (new_escapes, new_syntax): (Vec<_>, Vec<_>) =
+        new.into_iter()
+           .partition(|it| it.code == ErrorCode::InvalidEscape);
+    let mut errors = before.0;
+    errors.extend(new_syntax);
+    errors.extend(after.0);
+    errors.extend(before.1);
+    errors.extend(new_escapes);
+    errors.extend(after.1);
+    Some(errors)
+}
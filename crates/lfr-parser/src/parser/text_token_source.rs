@@ -1,5 +1,8 @@
 use lfr_stdx::TakeIfUnless;
-use lfr_syntax::rowan::TextSize;
+use lfr_syntax::rowan::{
+    TextRange,
+    TextSize,
+};
 use lfr_syntax::span::Span;
 use lfr_syntax::syntax_kind::SyntaxKind::EOF;
 use lfr_syntax::{
@@ -15,12 +18,14 @@ use super::{
 };
 use crate::parser::IsTrivia;
 
-///
+/// A [`TokenSource`] over the lexed tokens, with trivia filtered out.
 #[derive(Debug)]
 pub struct LexerWrap
 {
     pos:    usize,
     tokens: Vec<(Token, TextSize)>,
+    /// The length of the whole text.
+    len:    TextSize,
 }
 
 impl LexerWrap
@@ -38,7 +43,9 @@ impl LexerWrap
                                tk.take_unless(|tk| tk.0.syntax_kind.is_trivia())
                            })
                            .collect();
-        Self { pos: 0, tokens }
+        Self { pos: 0,
+               tokens,
+               len: text_off }
     }
 }
 
@@ -65,6 +72,21 @@ impl TokenSource for LexerWrap
         }
     }
 
+    fn is_joint_to_next(&self, n: usize) -> bool
+    {
+        self.tokens
+            .get(self.pos + n..)
+            .is_some_and(|tokens| is_joint(tokens, 0))
+    }
+
+    fn token_range(&self, pos: usize) -> TextRange
+    {
+        match self.tokens.get(pos) {
+            Some((token, offset)) => TextRange::at(*offset, token.len),
+            None => TextRange::empty(self.len),
+        }
+    }
+
     fn find(&self, find_property: FindProperty) -> ForwardToken
     {
         const NOT_FOUND: ForwardToken = ForwardToken { kind:   EOF,
@@ -82,6 +104,7 @@ impl TokenSource for LexerWrap
             tokens.len() >= 2
             && tokens[0].0.syntax_kind == kind1
             && tokens[1].0.syntax_kind == kind2
+            && is_joint(tokens, 0)
         }
 
         fn at_composite3(tokens: &[(Token, TextSize)],
@@ -94,6 +117,8 @@ impl TokenSource for LexerWrap
             && tokens[0].0.syntax_kind == kind1
             && tokens[1].0.syntax_kind == kind2
             && tokens[2].0.syntax_kind == kind3
+            && is_joint(tokens, 0)
+            && is_joint(tokens, 1)
         }
 
         fn at(tokens: &[(Token, TextSize)], kind: SyntaxKind) -> bool
@@ -117,6 +142,11 @@ impl TokenSource for LexerWrap
                 T![<=] => at_composite2(tokens, T![<], T![=]),
                 T![>=] => at_composite2(tokens, T![>], T![=]),
                 T![::] => at_composite2(tokens, T![:], T![:]),
+                T![..] => at_composite2(tokens, T![.], T![.]),
+                T![..=] => at_composite3(tokens, T![.], T![.], T![=]),
+                T![->] => at_composite2(tokens, T![-], T![>]),
+                T![=>] => at_composite2(tokens, T![=], T![>]),
+                T![|>] => at_composite2(tokens, T![|], T![>]),
                 kind => tokens[0].0.syntax_kind == kind,
             }
         }
@@ -139,7 +169,7 @@ impl TokenSource for LexerWrap
                 for tind in self.pos..self.tokens.len() {
                     let t = &self.tokens[tind..];
 
-                    if let None = kinds.iter().find(|&&it| at(t, it)) {
+                    if !kinds.iter().any(|&it| at(t, it)) {
                         return ForwardToken { kind:   t[0].0.syntax_kind,
                                               offset: tind - self.pos,
                                               state:  self.pos, }
@@ -186,3 +216,15 @@ impl TokenSource for LexerWrap
         forward_token.state + forward_token.offset - self.pos
     }
 }
+
+/// Whether `tokens[i]` ends where `tokens[i + 1]` starts, that is, whether
+/// the trivia that was filtered out didn't separate them.
+fn is_joint(tokens: &[(Token, TextSize)], i: usize) -> bool
+{
+    match (tokens.get(i), tokens.get(i + 1)) {
+        (Some((token, offset)), Some((_, next_offset))) => {
+            *offset + token.len == *next_offset
+        }
+        _ => false,
+    }
+}
@@ -6,14 +6,17 @@ use lfr_syntax::rowan::{
     TextRange,
     TextSize,
 };
-use lfr_syntax::syntax_kind::SyntaxKind;
+use lfr_syntax::syntax_kind::SyntaxKind::{
+    self,
+    *,
+};
 
+use super::IsTrivia;
 use super::error::ParseError;
 use super::token_source::Token;
 use super::tree_sink::TreeSink;
-use super::IsTrivia;
 
-///
+/// A [`TreeSink`] that builds a rowan green tree out of the source text.
 #[derive(Debug)]
 pub struct TextTreeSink<'sink>
 {
@@ -23,7 +26,7 @@ pub struct TextTreeSink<'sink>
     text_pos:  TextSize,
     token_pos: usize,
     state:     State,
-    errors:    Vec<(ParseError, TextSize)>,
+    errors:    Vec<ParseError>,
 }
 #[derive(Debug)]
 enum State
@@ -72,23 +75,18 @@ impl<'sink> TreeSink for TextTreeSink<'sink>
                                                      .count();
         let leading_trivias =
             &self.tokens[self.token_pos..self.token_pos + n_trivias];
-        let mut trivia_end =
-            self.text_pos
-            + leading_trivias.iter().map(|it| it.len).sum::<TextSize>();
-
         let n_attached_trivias =
-            {
-                let leading_trivias = leading_trivias.iter().rev().map(|it| {
-                let next_end = trivia_end - it.len;
-                let range = TextRange::new(next_end, trivia_end);
-                trivia_end = next_end;
-                (it.syntax_kind, &self.text[range])
-            });
-                n_attached_trivias(kind, leading_trivias)
-            };
+            n_attached_trivias(kind,
+                               leading_trivias.iter()
+                                              .rev()
+                                              .map(|it| it.syntax_kind));
         self.eat_n_trivias(n_trivias - n_attached_trivias);
         self.inner.start_node(kind.into());
-        self.eat_n_trivias(n_attached_trivias);
+        // a statement leaves the doc comments to the declaration it wraps,
+        // which starts right after it.
+        if kind != STMT {
+            self.eat_n_trivias(n_attached_trivias);
+        }
     }
 
     fn finish_node(&mut self)
@@ -100,10 +98,7 @@ impl<'sink> TreeSink for TextTreeSink<'sink>
         }
     }
 
-    fn error(&mut self, error: ParseError)
-    {
-        self.errors.push((error, self.text_pos))
-    }
+    fn error(&mut self, error: ParseError) { self.errors.push(error) }
 }
 
 impl<'sink> TextTreeSink<'sink>
@@ -119,7 +114,7 @@ impl<'sink> TextTreeSink<'sink>
                errors: vec![] }
     }
 
-    pub(super) fn finish(mut self) -> (GreenNode, Vec<(ParseError, TextSize)>)
+    pub(super) fn finish(mut self) -> (GreenNode, Vec<ParseError>)
     {
         match mem::replace(&mut self.state, State::Normal) {
             State::PendingFinish => {
@@ -161,43 +156,26 @@ impl<'sink> TextTreeSink<'sink>
     }
 }
 
-fn n_attached_trivias<'a>(kind: SyntaxKind,
-                          trivias: impl Iterator<Item = (SyntaxKind, &'a str)>)
-                          -> usize
+/// How many of `trivias`, the trivias before a node of `kind` from the
+/// closest one, go inside the node: the doc comments before a declaration,
+/// and the whitespace between them.
+fn n_attached_trivias(kind: SyntaxKind,
+                      trivias: impl Iterator<Item = SyntaxKind>)
+                      -> usize
 {
-    // match kind {
-    // TODO: attached trivias
-    //     EOF => {
-    //         let mut res = 0;
-    //         let mut trivias = trivias.enumerate().peekable();
-
-    //         while let Some((i, (kind, text))) = trivias.next() {
-    //             match kind {
-    //                 WHITESPACE if text.contains("\n\n") => {
-    //                     // we check whether the next token is a doc-comment
-    //                     // and skip the whitespace in this case
-    //                     if let Some((COMMENT, peek_text)) =
-    // trivias.peek().map(|(_, pair)| pair) {                         let
-    // comment_kind = ast::CommentKind::from_text(peek_text);
-    // if comment_kind.doc == Some(ast::CommentPlacement::Outer) {
-    //                             continue;
-    //                         }
-    //                     }
-    //                     break;
-    //                 }
-    //                 COMMENT => {
-    //                     let comment_kind = ast::CommentKind::from_text(text);
-    //                     if comment_kind.doc ==
-    // Some(ast::CommentPlacement::Inner) {                         break;
-    //                     }
-    //                     res = i + 1;
-    //                 }
-    //                 _ => (),
-    //             }
-    //         }
-    //         res
-    //     }
-    //     _ => 0,
-    // }
-    0
+    match kind {
+        STMT | FN_DEF | DECLARATION_STMT | STRUCT_DEF | ENUM_DEF
+        | EXPR_STMT => {
+            let mut res = 0;
+            for (i, kind) in trivias.enumerate() {
+                match kind {
+                    DOC_COMMENT | BLOCK_DOC_COMMENT => res = i + 1,
+                    WHITESPACE => {}
+                    _ => break,
+                }
+            }
+            res
+        }
+        _ => 0,
+    }
 }
@@ -0,0 +1,38 @@
+use lfr_syntax::SyntaxKind;
+
+/// A set of token kinds, checked in constant time. Composite tokens are
+/// never the current token of the parser, so they don't belong in a set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct TokenSet([u64; 3]);
+
+const _: () = assert!((SyntaxKind::__LAST as usize) < 3 * 64);
+
+impl TokenSet
+{
+    pub(crate) const EMPTY: TokenSet = TokenSet([0; 3]);
+
+    pub(crate) const fn new(kinds: &[SyntaxKind]) -> TokenSet
+    {
+        let mut res = [0; 3];
+        let mut i = 0;
+        while i < kinds.len() {
+            let kind = kinds[i] as usize;
+            res[kind / 64] |= 1 << (kind % 64);
+            i += 1;
+        }
+        TokenSet(res)
+    }
+
+    pub(crate) const fn union(self, other: TokenSet) -> TokenSet
+    {
+        TokenSet([self.0[0] | other.0[0],
+                  self.0[1] | other.0[1],
+                  self.0[2] | other.0[2]])
+    }
+
+    pub(crate) const fn contains(&self, kind: SyntaxKind) -> bool
+    {
+        let kind = kind as usize;
+        self.0[kind / 64] & (1 << (kind % 64)) != 0
+    }
+}
@@ -1,4 +1,7 @@
-use lfr_syntax::rowan::TextSize;
+use lfr_syntax::rowan::{
+    TextRange,
+    TextSize,
+};
 use lfr_syntax::syntax_kind::SyntaxKind;
 
 /// A structure to hold data about a forward token,
@@ -59,6 +62,12 @@ pub trait TokenSource
     fn lookahead(&self, n: usize) -> Token;
     /// Goes on to the next token.
     fn bump(&mut self);
+    /// Whether the `n`th token is directly followed by the next one, with no
+    /// trivia in between. Only joint tokens are glued into composites.
+    fn is_joint_to_next(&self, n: usize) -> bool;
+    /// The range of the `pos`th token since the start, an empty range at the
+    /// end of the text for a `pos` past the last token.
+    fn token_range(&self, pos: usize) -> TextRange;
 
     /// Finds the token with the given property.
     fn find(&self, find_property: FindProperty) -> ForwardToken;
@@ -0,0 +1,380 @@
+//! Runs the parser over `test_data/parser`, comparing the trees against the
+//! committed `.rast` dumps. Set `BLESS=1` to write the dumps instead.
+//!
+//! The property tests feed the parser random token soup and mangled test
+//! data, and check that reparsing after an edit matches a full parse. proptest
+//! records the inputs it shrinks failures down to under `proptest-regressions`;
+//! inputs found by `cargo fuzz` belong, minimized, in `test_data/parser/
+//! fuzz-failures`.
+
+use std::fmt::Write;
+use std::path::{
+    Path,
+    PathBuf,
+};
+use std::{
+    env,
+    fs,
+};
+
+use lfr_syntax::ast::{
+    self,
+    AstNode,
+    DocCommentsOwner,
+};
+use lfr_syntax::rowan::TextRange;
+use lfr_syntax::{
+    SyntaxKind,
+    SyntaxNode,
+};
+use proptest::prelude::*;
+use proptest::sample::Index;
+
+use crate::fuzz::{
+    check_parser,
+    check_reparse,
+};
+use crate::parser::error::ErrorCode;
+use crate::parser::reparsing::{
+    TextEdit,
+    incremental_reparse,
+};
+use crate::parser::{
+    debug_ast,
+    parse,
+    parse_block_fragment,
+    parse_expr_fragment,
+    parse_stmt_fragment,
+    parse_type_fragment,
+};
+
+#[test]
+fn parser_inline_ok() { dir_tests("inline/ok", true) }
+
+#[test]
+fn parser_inline_err() { dir_tests("inline/err", false) }
+
+#[test]
+fn parser_fuzz_failures()
+{
+    for file in lfr_files(&test_data_dir().join("fuzz-failures")) {
+        check_parser(&fs::read_to_string(file).unwrap());
+    }
+}
+
+#[test]
+fn reparse_is_incremental()
+{
+    let cases = [// relexes the identifier
+                 ("x = foo + 1\n", 4..7, "bar"),
+                 ("x = 1 // one\n", 9..12, "uno"),
+                 // reparses the block
+                 ("fn f() {\n    x = 1\n}\n", 17..18, "2 + 3"),
+                 ("fn f() {\n    x = 1\n}\n", 13..13, "y = [\n"),
+                 // reparses the array, with the block around it untouched
+                 ("if a {\n    x = [1, 2]\n}\n", 16..17, "4, 5"),
+                 // reparses the tuple, its errors replacing the old ones
+                 ("x = (1 2)\ny = 3\n", 6..6, ",")];
+    for (text, delete, insert) in cases {
+        let edit = TextEdit::new(TextRange::new(delete.start.into(),
+                                                delete.end.into()),
+                                 insert);
+        let (green, errors) = parse(text);
+        let root = SyntaxNode::new_root(green);
+        assert!(incremental_reparse(&root, &errors, &edit, &edit.apply(text))
+                .is_some(),
+                "{:?} wasn't reparsed incrementally after {:?}",
+                text,
+                edit);
+        check_reparse(text, &edit);
+    }
+}
+
+#[test]
+fn fragments()
+{
+    let (expr, errors) = parse_expr_fragment("a + f(1)\n").unwrap();
+    assert_eq!(expr.syntax().kind(),
+               SyntaxKind::BIN_EXPR);
+    assert!(errors.is_empty());
+
+    let (_, errors) = parse_expr_fragment("a +").unwrap();
+    assert_eq!(errors[0].code, ErrorCode::ExpectedExpr);
+
+    let errors = parse_expr_fragment("a b c").unwrap_err();
+    let trailing = errors.last().unwrap();
+    assert_eq!(trailing.code, ErrorCode::TrailingTokens);
+    assert_eq!(trailing.range,
+               TextRange::new(2.into(), 5.into()));
+
+    let (stmt, errors) = parse_stmt_fragment("\nlet x: int = 1").unwrap();
+    assert!(matches!(stmt.inner(),
+                     Some(ast::StmtInner::DeclarationStmt(_))));
+    assert_eq!(stmt.syntax().text_range(),
+               TextRange::new(1.into(), 15.into()));
+    assert!(errors.is_empty());
+    assert!(parse_stmt_fragment("x = 1\ny = 2").is_err());
+
+    let (block, errors) = parse_block_fragment("{\n    x = 1\n}").unwrap();
+    assert_eq!(block.syntax().text(), "{\n    x = 1\n}");
+    assert!(errors.is_empty());
+
+    let (ty, errors) = parse_type_fragment("map<str, list<int>>").unwrap();
+    assert_eq!(ty.syntax().kind(), SyntaxKind::TYPE_REF);
+    assert!(errors.is_empty());
+    assert!(parse_type_fragment("int = 1").is_err());
+}
+
+#[test]
+fn deep_nesting_is_an_error()
+{
+    let deep = |open: &str, inner: &str, close: &str| {
+        format!("{}{}{}",
+                open.repeat(1000),
+                inner,
+                close.repeat(1000))
+    };
+    let cases = [format!("x = {}\ny = 2\n", deep("(", "1", ")")),
+                 format!("x = {}\ny = 2\n", deep("-", "1", "")),
+                 format!("x = {}\ny = 2\n", "-".repeat(50_000)),
+                 format!("x = {}\ny = 2\n", deep("[", "", "]")),
+                 format!("x = {}\ny = 2\n",
+                         deep("a ? ", "b", " : c")),
+                 format!("{}\ny = 2\n", deep("{", "", "}")),
+                 format!("let x: {} = 1\ny = 2\n",
+                         deep("list<", "int", ">")),
+                 format!("match x {{ {} => 1 }}\ny = 2\n",
+                         deep("(", "a", ")"))];
+    for text in &cases {
+        let (green, errors) = parse(text);
+        let codes = errors.iter().map(|it| it.code).collect::<Vec<_>>();
+        assert_eq!(codes,
+                   [ErrorCode::NestingTooDeep],
+                   "{}",
+                   &text[..20]);
+        assert_eq!(SyntaxNode::new_root(green).text(),
+                   text.as_str());
+    }
+
+    // unclosed, the brackets report the rest
+    let (_, errors) = parse(&"(".repeat(50_000));
+    assert_eq!(errors[0].code,
+               ErrorCode::NestingTooDeep);
+
+    // the array is well within the limit on its own, not where it sits
+    let text = format!("x = {}[1]{}\n", "(".repeat(60), ")".repeat(60));
+    let edit = TextEdit::new(TextRange::at(65.into(), 0.into()),
+                             "[[[[[[[[]]]]]]]], ");
+    check_reparse(&text, &edit);
+}
+
+#[test]
+fn doc_comment_text()
+{
+    let text = concat!("/// Compiles `src`.\n///\n///  Indented.\n",
+                       "fn cc(src) {}\n",
+                       "/**\n * The app.\n *\n * Built on linux.\n */\n",
+                       "let app = 1\n",
+                       "/** One line. */\nstruct Opts {}\n",
+                       "enum Kind { Lib }\n");
+    let (green, errors) = parse(text);
+    assert!(errors.is_empty(), "{:?}", errors);
+    let root = SyntaxNode::new_root(green);
+
+    assert_eq!(doc_comment_of::<ast::FnDef>(&root).as_deref(),
+               Some("Compiles `src`.\n\n Indented."));
+    assert_eq!(doc_comment_of::<ast::DeclarationStmt>(&root).as_deref(),
+               Some("The app.\n\nBuilt on linux."));
+    assert_eq!(doc_comment_of::<ast::StructDef>(&root).as_deref(),
+               Some("One line."));
+    assert_eq!(doc_comment_of::<ast::EnumDef>(&root),
+               None);
+}
+
+#[test]
+fn shebang_and_pragmas()
+{
+    let text = concat!("#!/usr/bin/env lfr\r\n",
+                       "@!lfr('>=0.4')\n@!edition('2024')\n",
+                       "x = 1\n");
+    let (green, errors) = parse(text);
+    assert!(errors.is_empty(), "{:?}", errors);
+    let root = ast::Root::cast(SyntaxNode::new_root(green)).unwrap();
+
+    assert_eq!(root.shebang_token().unwrap().text(),
+               "#!/usr/bin/env lfr");
+    let edition = root.pragma("edition").and_then(|it| it.fn_call_args());
+    assert_eq!(edition.unwrap().syntax().text(),
+               "('2024')");
+    assert!(root.pragma("deprecated").is_none());
+
+    // only the start of a file can be a shebang
+    let (_, errors) = parse("x = 1\n#!/usr/bin/env lfr\n");
+    assert!(!errors.is_empty());
+}
+
+/// The doc comment text of the first `N` in `root`.
+fn doc_comment_of<N: DocCommentsOwner>(root: &SyntaxNode) -> Option<String>
+{
+    root.descendants().find_map(N::cast)?.doc_comment_text()
+}
+
+/// Bits of source the random inputs are made of, chosen to hit the
+/// composite punctuation and the string lexing.
+const TOKENS: &[&str] =
+    &["fn", "this", "while", "for", "in", "continue", "break", "return", "if",
+      "else", "let", "true", "false", "import", "as", "struct", "enum", "x",
+      "_", "1", "0x1f", "1.5e3", "c'a'", "'a'", "\"b\"", "'''", "r#\"", "\"#",
+      "'${", "${", "}", "{", "(", ")", "[", "]", "<", ">", ",", ";", ":", "=",
+      "+", "-", "*", "/", "%", "!", "&", "|", "^", ".", "$", "\\", "\n", " ",
+      "// c\n", "/// d\n", "/*", "/**", "*/", "@", "#!", "é"];
+
+fn token_soup() -> impl Strategy<Value = String>
+{
+    prop::collection::vec(prop::sample::select(TOKENS), 0..64).prop_map(|it| {
+                                                                  it.concat()
+                                                              })
+}
+
+/// The texts of the inline tests.
+fn test_data() -> Vec<String>
+{
+    let mut files = vec![];
+    for dir in ["inline/ok", "inline/err"] {
+        for file in lfr_files(&test_data_dir().join(dir)) {
+            files.push(fs::read_to_string(file).unwrap());
+        }
+    }
+    files
+}
+
+/// A test-data file with a few ranges replaced by random tokens.
+fn mutated_test_data() -> impl Strategy<Value = String>
+{
+    let edit = (any::<Index>(), 0..8usize, prop::sample::select(TOKENS));
+    (prop::sample::select(test_data()), prop::collection::vec(edit, 1..8))
+        .prop_map(|(mut text, edits)| {
+            for (at, len, token) in edits {
+                let start = char_boundary(&text, at.index(text.len() + 1));
+                let end = char_boundary(&text, start + len);
+                text.replace_range(start..end, token);
+            }
+            text
+        })
+}
+
+/// A test-data file along with an edit replacing a random range by a random
+/// token.
+fn edited_test_data() -> impl Strategy<Value = (String, TextEdit)>
+{
+    let edit = (any::<Index>(), 0..8usize, prop::sample::select(TOKENS));
+    (prop::sample::select(test_data()), edit).prop_map(|(text,
+                                                         (at, len, token))| {
+        let start = char_boundary(&text, at.index(text.len() + 1));
+        let end = char_boundary(&text, start + len);
+        let delete =
+            TextRange::new((start as u32).into(), (end as u32).into());
+        (text, TextEdit::new(delete, token))
+    })
+}
+
+/// The char boundary at or before `offset`, clamped to the end of `text`.
+fn char_boundary(text: &str, offset: usize) -> usize
+{
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+proptest! {
+    #[test]
+    fn parser_is_lossless_on_token_soup(text in token_soup())
+    {
+        check_parser(&text);
+    }
+
+    #[test]
+    fn parser_is_lossless_on_mutated_test_data(text in mutated_test_data())
+    {
+        check_parser(&text);
+    }
+
+    #[test]
+    fn reparse_matches_full_parse((text, edit) in edited_test_data())
+    {
+        check_reparse(&text, &edit);
+    }
+}
+
+/// The dump of the tree `text` parses into, followed by its errors.
+fn dump(text: &str) -> (String, usize)
+{
+    let (green, errors) = parse(text);
+    let mut buf = debug_ast(&SyntaxNode::new_root(green));
+    for err in &errors {
+        writeln!(buf, "error {:?}: {}", err.range, err).unwrap();
+        if !err.expected.is_empty() {
+            writeln!(buf, "  expected {:?}", err.expected).unwrap();
+        }
+        for label in &err.labels {
+            writeln!(buf,
+                     "  label {:?}: {}",
+                     label.range, label.message).unwrap();
+        }
+    }
+    (buf, errors.len())
+}
+
+fn dir_tests(dir: &str, ok: bool)
+{
+    let bless = env::var_os("BLESS").is_some();
+    let mut failures = vec![];
+    for file in lfr_files(&test_data_dir().join(dir)) {
+        let text = fs::read_to_string(&file).unwrap();
+        let (actual, errors) = dump(&text);
+        if ok && errors > 0 {
+            failures.push(format!("{}: unexpected errors", file.display()));
+        }
+        if !ok && errors == 0 {
+            failures.push(format!("{}: no errors", file.display()));
+        }
+
+        let rast = file.with_extension("rast");
+        let expected = fs::read_to_string(&rast).unwrap_or_default();
+        if expected == actual {
+            continue;
+        }
+        if bless {
+            fs::write(&rast, &actual).unwrap();
+        }
+        else {
+            failures.push(format!("{}: tree differs from {}",
+                                  file.display(),
+                                  rast.display()));
+        }
+    }
+
+    assert!(failures.is_empty(),
+            "\n{}\n(run with BLESS=1 to update the .rast files)",
+            failures.join("\n"));
+}
+
+fn test_data_dir() -> PathBuf
+{
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../test_data/parser")
+}
+
+fn lfr_files(dir: &Path) -> Vec<PathBuf>
+{
+    let mut files =
+        fs::read_dir(dir).unwrap()
+                         .map(|it| it.unwrap().path())
+                         .filter(|it| {
+                             it.extension().is_some_and(|it| it == "lfr")
+                         })
+                         .collect::<Vec<_>>();
+    files.sort();
+    files
+}
@@ -12,9 +12,21 @@ mod generated
     pub mod nodes;
     pub mod tokens;
 }
+mod doc_comments;
+mod expr_ext;
+mod string_lit;
 
+pub use doc_comments::{
+    DocCommentIter,
+    DocCommentsOwner,
+};
 pub use generated::nodes::*;
 pub use generated::tokens::*;
+pub use string_lit::{
+    EscapeError,
+    EscapeErrorKind,
+    InterpolatedStrPart,
+};
 
 pub trait AstNode
 {
@@ -75,6 +87,12 @@ impl<N: AstNode> Iterator for AstChildren<N>
     fn next(&mut self) -> Option<N> { self.inner.find_map(N::cast) }
 }
 
+/// A statement or declaration that attributes, `@name(args)`, can precede.
+pub trait HasAttrs: AstNode
+{
+    fn attrs(&self) -> AstChildren<Attr> { support::children(self.syntax()) }
+}
+
 mod support
 {
     use super::{
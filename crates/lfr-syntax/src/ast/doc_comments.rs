@@ -0,0 +1,121 @@
+//! Doc comments, `///` and `/** */`, on the declarations after them.
+
+use super::{
+    AstNode,
+    DeclarationStmt,
+    EnumDef,
+    ExprStmt,
+    FnDef,
+    StructDef,
+};
+use crate::{
+    SyntaxElementChildren,
+    SyntaxKind,
+    SyntaxToken,
+};
+
+/// A declaration that holds the doc comments before it, ahead of its other
+/// children.
+///
+/// A function with attributes is in an [`ExprStmt`] along with them, which
+/// gets the doc comments before the attributes.
+pub trait DocCommentsOwner: AstNode
+{
+    fn doc_comments(&self) -> DocCommentIter
+    {
+        DocCommentIter { iter: Some(self.syntax().children_with_tokens()), }
+    }
+
+    /// The text of the doc comments without the `///`, `/**` and `*/`, and
+    /// without the `*` that lines in a `/** */` usually start with, or `None`
+    /// if there are none.
+    fn doc_comment_text(&self) -> Option<String>
+    {
+        let mut lines = vec![];
+        for comment in self.doc_comments() {
+            doc_lines(&comment, &mut lines);
+        }
+        if lines.is_empty() {
+            return None
+        }
+        Some(lines.join("\n"))
+    }
+}
+
+impl DocCommentsOwner for FnDef {}
+impl DocCommentsOwner for DeclarationStmt {}
+impl DocCommentsOwner for StructDef {}
+impl DocCommentsOwner for EnumDef {}
+impl DocCommentsOwner for ExprStmt {}
+
+/// An iterator over the doc comments a [`DocCommentsOwner`] starts with.
+#[derive(Debug, Clone)]
+pub struct DocCommentIter
+{
+    /// `None` once something other than a doc comment or whitespace was
+    /// reached.
+    iter: Option<SyntaxElementChildren>,
+}
+
+impl Iterator for DocCommentIter
+{
+    type Item = SyntaxToken;
+
+    fn next(&mut self) -> Option<SyntaxToken>
+    {
+        while let Some(it) = self.iter.as_mut()?.next() {
+            match it.into_token() {
+                Some(token) if is_doc_comment(token.kind()) => {
+                    return Some(token)
+                }
+                Some(token) if token.kind() == SyntaxKind::WHITESPACE => {}
+                _ => break,
+            }
+        }
+        self.iter = None;
+        None
+    }
+}
+
+fn is_doc_comment(kind: SyntaxKind) -> bool
+{
+    matches!(kind,
+             SyntaxKind::DOC_COMMENT | SyntaxKind::BLOCK_DOC_COMMENT)
+}
+
+/// Pushes the lines of documentation in `comment` onto `lines`.
+fn doc_lines(comment: &SyntaxToken, lines: &mut Vec<String>)
+{
+    // doc comments end with the newline after them
+    let text = comment.text().trim_end_matches(['\r', '\n']);
+
+    if comment.kind() == SyntaxKind::DOC_COMMENT {
+        lines.push(strip_space(&text["///".len()..]).trim_end().to_owned());
+        return
+    }
+
+    let text = &text["/**".len()..];
+    let text = text.strip_suffix("*/").unwrap_or(text);
+    let block = text.lines()
+                    .enumerate()
+                    .map(|(i, line)| {
+                        let line = match i {
+                            0 => line,
+                            _ => {
+                                let line = line.trim_start();
+                                line.strip_prefix('*').unwrap_or(line)
+                            }
+                        };
+                        strip_space(line).trim_end().to_owned()
+                    })
+                    .collect::<Vec<_>>();
+    // the lines of `/**` and `*/`, when they have nothing else
+    let start = block.iter().position(|it| !it.is_empty());
+    let end = block.iter().rposition(|it| !it.is_empty());
+    if let (Some(start), Some(end)) = (start, end) {
+        lines.extend_from_slice(&block[start..=end]);
+    }
+}
+
+/// `line` without the space that usually follows the comment markers.
+fn strip_space(line: &str) -> &str { line.strip_prefix(' ').unwrap_or(line) }
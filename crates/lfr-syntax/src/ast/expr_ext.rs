@@ -0,0 +1,103 @@
+//! Accessors the generator can't derive from `lfr.ungram`.
+
+use super::{
+    AstChildren,
+    AstNode,
+    BinExpr,
+    Expr,
+    MapEntry,
+    PipeExpr,
+    Pragma,
+    PrefixUnaryExpr,
+    RangeExpr,
+    Root,
+    TernaryExpr,
+    support,
+};
+use crate::{
+    SyntaxKind,
+    SyntaxToken,
+};
+
+impl BinExpr
+{
+    pub fn lhs(&self) -> Option<Expr> { self.operands().next() }
+
+    pub fn rhs(&self) -> Option<Expr> { self.operands().nth(1) }
+
+    /// The operator, composite operators being a single token.
+    pub fn op_token(&self) -> Option<SyntaxToken> { op_token(self.syntax()) }
+
+    fn operands(&self) -> AstChildren<Expr> { support::children(self.syntax()) }
+}
+
+impl MapEntry
+{
+    pub fn key(&self) -> Option<Expr> { self.exprs().next() }
+
+    pub fn value(&self) -> Option<Expr> { self.exprs().nth(1) }
+
+    fn exprs(&self) -> AstChildren<Expr> { support::children(self.syntax()) }
+}
+
+impl PipeExpr
+{
+    pub fn lhs(&self) -> Option<Expr> { self.operands().next() }
+
+    pub fn rhs(&self) -> Option<Expr> { self.operands().nth(1) }
+
+    fn operands(&self) -> AstChildren<Expr> { support::children(self.syntax()) }
+}
+
+impl PrefixUnaryExpr
+{
+    pub fn op_token(&self) -> Option<SyntaxToken> { op_token(self.syntax()) }
+}
+
+impl RangeExpr
+{
+    pub fn start(&self) -> Option<Expr> { self.bounds().next() }
+
+    pub fn end(&self) -> Option<Expr> { self.bounds().nth(1) }
+
+    /// `..` or `..=`, as a single token.
+    pub fn op_token(&self) -> Option<SyntaxToken> { op_token(self.syntax()) }
+
+    fn bounds(&self) -> AstChildren<Expr> { support::children(self.syntax()) }
+}
+
+impl TernaryExpr
+{
+    pub fn cond(&self) -> Option<Expr> { self.exprs().next() }
+
+    pub fn then_expr(&self) -> Option<Expr> { self.exprs().nth(1) }
+
+    pub fn else_expr(&self) -> Option<Expr> { self.exprs().nth(2) }
+
+    fn exprs(&self) -> AstChildren<Expr> { support::children(self.syntax()) }
+}
+
+impl Root
+{
+    /// The pragma named `name`, as `edition` for `@!edition('2024')`.
+    pub fn pragma(&self, name: &str) -> Option<Pragma>
+    {
+        self.pragmas()
+            .find(|it| it.ident_token().is_some_and(|it| it.text() == name))
+    }
+}
+
+fn op_token(node: &crate::SyntaxNode) -> Option<SyntaxToken>
+{
+    node.children_with_tokens()
+        .filter_map(|it| it.into_token())
+        .find(|it| {
+            !matches!(it.kind(),
+                      SyntaxKind::WHITESPACE
+                      | SyntaxKind::COMMENT
+                      | SyntaxKind::BLOCK_COMMENT
+                      | SyntaxKind::DOC_COMMENT
+                      | SyntaxKind::BLOCK_DOC_COMMENT
+                      | SyntaxKind::NEWLINE)
+        })
+}
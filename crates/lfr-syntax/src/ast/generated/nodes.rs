@@ -2,15 +2,15 @@
 
 #![allow(unused_imports)]
 
+use crate::SyntaxKind::{
+    self,
+    *,
+};
 use crate::ast::{
     self,
-    support,
     AstChildren,
     AstNode,
-};
-use crate::SyntaxKind::{
-    self,
-    *,
+    support,
 };
 use crate::{
     SyntaxNode,
@@ -24,6 +24,16 @@ pub struct Root
 }
 impl Root
 {
+    pub fn shebang_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![shebang])
+    }
+
+    pub fn pragmas(&self) -> AstChildren<Pragma>
+    {
+        support::children(&self.syntax)
+    }
+
     pub fn import_stmts(&self) -> AstChildren<ImportStmt>
     {
         support::children(&self.syntax)
@@ -32,6 +42,33 @@ impl Root
     pub fn stmts(&self) -> AstChildren<Stmt> { support::children(&self.syntax) }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Pragma
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl Pragma
+{
+    pub fn at_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T ! [@])
+    }
+
+    pub fn bang_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![!])
+    }
+
+    pub fn ident_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![ident])
+    }
+
+    pub fn fn_call_args(&self) -> Option<FnCallArgs>
+    {
+        support::child(&self.syntax)
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ImportStmt
 {
     pub(crate) syntax: SyntaxNode,
@@ -49,6 +86,36 @@ impl ImportStmt
     }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Stmt
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl Stmt
+{
+    pub fn expr(&self) -> Option<Expr> { support::child(&self.syntax) }
+
+    pub fn inner(&self) -> Option<StmtInner> { support::child(&self.syntax) }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FnCallArgs
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl FnCallArgs
+{
+    pub fn l_paren_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T!['('])
+    }
+
+    pub fn exprs(&self) -> AstChildren<Expr> { support::children(&self.syntax) }
+
+    pub fn r_paren_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![')'])
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ImportTarget
 {
     pub(crate) syntax: SyntaxNode,
@@ -86,6 +153,11 @@ impl StringLit
     {
         support::token(&self.syntax, T![multiline_str])
     }
+
+    pub fn raw_str_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![raw_str])
+    }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Name
@@ -105,11 +177,58 @@ impl Name
     }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InterpolatedStr
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl InterpolatedStr
+{
+    pub fn str_start_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![str_start])
+    }
+
+    pub fn str_middle_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![str_middle])
+    }
+
+    pub fn exprs(&self) -> AstChildren<Expr> { support::children(&self.syntax) }
+
+    pub fn str_end_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![str_end])
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct NumberLit
 {
     pub(crate) syntax: SyntaxNode,
 }
-impl NumberLit {}
+impl NumberLit
+{
+    pub fn int_number_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![int_number])
+    }
+
+    pub fn float_number_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![float_number])
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CharLit
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl CharLit
+{
+    pub fn char_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![char])
+    }
+}
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct BooleanLit
 {
@@ -137,6 +256,28 @@ impl LitVal
     pub fn inner(&self) -> Option<LitValInner> { support::child(&self.syntax) }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Attr
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl Attr
+{
+    pub fn at_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T ! [@])
+    }
+
+    pub fn ident_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![ident])
+    }
+
+    pub fn fn_call_args(&self) -> Option<FnCallArgs>
+    {
+        support::child(&self.syntax)
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct FnDef
 {
     pub(crate) syntax: SyntaxNode,
@@ -153,17 +294,55 @@ impl FnDef
         support::token(&self.syntax, T![ident])
     }
 
+    pub fn param_list(&self) -> Option<ParamList>
+    {
+        support::child(&self.syntax)
+    }
+
+    pub fn ret_type(&self) -> Option<RetType> { support::child(&self.syntax) }
+
+    pub fn block(&self) -> Option<Block> { support::child(&self.syntax) }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParamList
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl ParamList
+{
     pub fn l_paren_token(&self) -> Option<SyntaxToken>
     {
         support::token(&self.syntax, T!['('])
     }
 
+    pub fn params(&self) -> AstChildren<Param>
+    {
+        support::children(&self.syntax)
+    }
+
     pub fn r_paren_token(&self) -> Option<SyntaxToken>
     {
         support::token(&self.syntax, T![')'])
     }
 
-    pub fn block(&self) -> Option<Block> { support::child(&self.syntax) }
+    pub fn pipe_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T ! [|])
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RetType
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl RetType
+{
+    pub fn thin_arrow_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T ! [->])
+    }
+
+    pub fn type_ref(&self) -> Option<TypeRef> { support::child(&self.syntax) }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Block
@@ -185,144 +364,174 @@ impl Block
     }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct ExprStmt
+pub struct Param
 {
     pub(crate) syntax: SyntaxNode,
 }
-impl ExprStmt
+impl Param
 {
-    pub fn expr(&self) -> Option<Expr> { support::child(&self.syntax) }
+    pub fn ident_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![ident])
+    }
 
-    pub fn semicolon_token(&self) -> Option<SyntaxToken>
+    pub fn colon_token(&self) -> Option<SyntaxToken>
     {
-        support::token(&self.syntax, T ! [;])
+        support::token(&self.syntax, T ! [:])
+    }
+
+    pub fn type_ref(&self) -> Option<TypeRef> { support::child(&self.syntax) }
+
+    pub fn eq_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T ! [=])
     }
+
+    pub fn default(&self) -> Option<Expr> { support::child(&self.syntax) }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct DeclarationStmt
+pub struct TypeRef
 {
     pub(crate) syntax: SyntaxNode,
 }
-impl DeclarationStmt
+impl TypeRef
 {
-    pub fn let_token(&self) -> Option<SyntaxToken>
-    {
-        support::token(&self.syntax, T![let])
-    }
-
-    pub fn ident_token(&self) -> Option<SyntaxToken>
+    pub fn bang_token(&self) -> Option<SyntaxToken>
     {
-        support::token(&self.syntax, T![ident])
+        support::token(&self.syntax, T![!])
     }
 
-    pub fn eq_token(&self) -> Option<SyntaxToken>
+    pub fn underscore_token(&self) -> Option<SyntaxToken>
     {
-        support::token(&self.syntax, T ! [=])
+        support::token(&self.syntax, T![_])
     }
 
-    pub fn expr(&self) -> Option<Expr> { support::child(&self.syntax) }
+    pub fn path(&self) -> Option<Path> { support::child(&self.syntax) }
 
-    pub fn semicolon_token(&self) -> Option<SyntaxToken>
+    pub fn generic_arg_list(&self) -> Option<GenericArgList>
     {
-        support::token(&self.syntax, T ! [;])
+        support::child(&self.syntax)
     }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct WhileStmt
+pub struct StructDef
 {
     pub(crate) syntax: SyntaxNode,
 }
-impl WhileStmt
+impl ast::HasAttrs for StructDef {}
+impl StructDef
 {
-    pub fn while_token(&self) -> Option<SyntaxToken>
+    pub fn struct_token(&self) -> Option<SyntaxToken>
     {
-        support::token(&self.syntax, T![while])
+        support::token(&self.syntax, T![struct])
     }
 
-    pub fn expr(&self) -> Option<Expr> { support::child(&self.syntax) }
+    pub fn ident_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![ident])
+    }
 
-    pub fn block(&self) -> Option<Block> { support::child(&self.syntax) }
+    pub fn field_list(&self) -> Option<FieldList>
+    {
+        support::child(&self.syntax)
+    }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct ForStmt
+pub struct FieldList
 {
     pub(crate) syntax: SyntaxNode,
 }
-impl ForStmt
+impl FieldList
 {
-    pub fn for_token(&self) -> Option<SyntaxToken>
+    pub fn l_curly_token(&self) -> Option<SyntaxToken>
     {
-        support::token(&self.syntax, T![for])
+        support::token(&self.syntax, T!['{'])
     }
 
-    pub fn ident_token(&self) -> Option<SyntaxToken>
+    pub fn field_defs(&self) -> AstChildren<FieldDef>
     {
-        support::token(&self.syntax, T![ident])
+        support::children(&self.syntax)
     }
 
-    pub fn in_token(&self) -> Option<SyntaxToken>
+    pub fn r_curly_token(&self) -> Option<SyntaxToken>
     {
-        support::token(&self.syntax, T![in])
+        support::token(&self.syntax, T!['}'])
     }
-
-    pub fn expr(&self) -> Option<Expr> { support::child(&self.syntax) }
-
-    pub fn block(&self) -> Option<Block> { support::child(&self.syntax) }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct BinExpr
+pub struct FieldDef
 {
     pub(crate) syntax: SyntaxNode,
 }
-impl BinExpr {}
+impl FieldDef
+{
+    pub fn ident_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![ident])
+    }
+
+    pub fn colon_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T ! [:])
+    }
+
+    pub fn type_ref(&self) -> Option<TypeRef> { support::child(&self.syntax) }
+}
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct TupleExpr
+pub struct EnumDef
 {
     pub(crate) syntax: SyntaxNode,
 }
-impl TupleExpr
+impl ast::HasAttrs for EnumDef {}
+impl EnumDef
 {
-    pub fn l_paren_token(&self) -> Option<SyntaxToken>
+    pub fn enum_token(&self) -> Option<SyntaxToken>
     {
-        support::token(&self.syntax, T!['('])
+        support::token(&self.syntax, T![enum])
     }
 
-    pub fn exprs(&self) -> AstChildren<Expr> { support::children(&self.syntax) }
+    pub fn ident_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![ident])
+    }
 
-    pub fn r_paren_token(&self) -> Option<SyntaxToken>
+    pub fn variant_list(&self) -> Option<VariantList>
     {
-        support::token(&self.syntax, T![')'])
+        support::child(&self.syntax)
     }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct ArrExpr
+pub struct VariantList
 {
     pub(crate) syntax: SyntaxNode,
 }
-impl ArrExpr
+impl VariantList
 {
-    pub fn l_brack_token(&self) -> Option<SyntaxToken>
+    pub fn l_curly_token(&self) -> Option<SyntaxToken>
     {
-        support::token(&self.syntax, T!['['])
+        support::token(&self.syntax, T!['{'])
     }
 
-    pub fn exprs(&self) -> AstChildren<Expr> { support::children(&self.syntax) }
+    pub fn variants(&self) -> AstChildren<Variant>
+    {
+        support::children(&self.syntax)
+    }
 
-    pub fn r_brack_token(&self) -> Option<SyntaxToken>
+    pub fn r_curly_token(&self) -> Option<SyntaxToken>
     {
-        support::token(&self.syntax, T![']'])
+        support::token(&self.syntax, T!['}'])
     }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct PrimaryExpr
+pub struct Variant
 {
     pub(crate) syntax: SyntaxNode,
 }
-impl PrimaryExpr
+impl Variant
 {
-    pub fn inner(&self) -> Option<PrimaryExprInner>
+    pub fn ident_token(&self) -> Option<SyntaxToken>
     {
-        support::child(&self.syntax)
+        support::token(&self.syntax, T![ident])
     }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -348,15 +557,270 @@ impl Path
     }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct IfExpr
+pub struct GenericArgList
 {
     pub(crate) syntax: SyntaxNode,
 }
-impl IfExpr
+impl GenericArgList
 {
-    pub fn if_branch(&self) -> Option<IfBranch> { support::child(&self.syntax) }
+    pub fn l_angle_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T ! [<])
+    }
 
-    pub fn else_if_branchs(&self) -> AstChildren<ElseIfBranch>
+    pub fn type_refs(&self) -> AstChildren<TypeRef>
+    {
+        support::children(&self.syntax)
+    }
+
+    pub fn r_angle_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T ! [>])
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExprStmt
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl ast::HasAttrs for ExprStmt {}
+impl ExprStmt
+{
+    pub fn expr(&self) -> Option<Expr> { support::child(&self.syntax) }
+
+    pub fn semicolon_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T ! [;])
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DeclarationStmt
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl ast::HasAttrs for DeclarationStmt {}
+impl DeclarationStmt
+{
+    pub fn let_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![let])
+    }
+
+    pub fn ident_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![ident])
+    }
+
+    pub fn colon_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T ! [:])
+    }
+
+    pub fn type_ref(&self) -> Option<TypeRef> { support::child(&self.syntax) }
+
+    pub fn eq_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T ! [=])
+    }
+
+    pub fn expr(&self) -> Option<Expr> { support::child(&self.syntax) }
+
+    pub fn semicolon_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T ! [;])
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WhileStmt
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl ast::HasAttrs for WhileStmt {}
+impl WhileStmt
+{
+    pub fn while_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![while])
+    }
+
+    pub fn expr(&self) -> Option<Expr> { support::child(&self.syntax) }
+
+    pub fn block(&self) -> Option<Block> { support::child(&self.syntax) }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ForStmt
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl ast::HasAttrs for ForStmt {}
+impl ForStmt
+{
+    pub fn for_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![for])
+    }
+
+    pub fn pat(&self) -> Option<Pat> { support::child(&self.syntax) }
+
+    pub fn in_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![in])
+    }
+
+    pub fn expr(&self) -> Option<Expr> { support::child(&self.syntax) }
+
+    pub fn block(&self) -> Option<Block> { support::child(&self.syntax) }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BinExpr
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl BinExpr {}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeExpr
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl RangeExpr {}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PipeExpr
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl PipeExpr
+{
+    pub fn pipe_r_angle_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T ! [|>])
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TernaryExpr
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl TernaryExpr
+{
+    pub fn qmark_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T ! [?])
+    }
+
+    pub fn colon_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T ! [:])
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TupleExpr
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl TupleExpr
+{
+    pub fn l_paren_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T!['('])
+    }
+
+    pub fn exprs(&self) -> AstChildren<Expr> { support::children(&self.syntax) }
+
+    pub fn r_paren_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![')'])
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArrExpr
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl ArrExpr
+{
+    pub fn l_brack_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T!['['])
+    }
+
+    pub fn exprs(&self) -> AstChildren<Expr> { support::children(&self.syntax) }
+
+    pub fn r_brack_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![']'])
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MapExpr
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl MapExpr
+{
+    pub fn percent_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T ! [%])
+    }
+
+    pub fn l_curly_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T!['{'])
+    }
+
+    pub fn map_entrys(&self) -> AstChildren<MapEntry>
+    {
+        support::children(&self.syntax)
+    }
+
+    pub fn r_curly_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T!['}'])
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MapEntry
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl MapEntry
+{
+    pub fn colon_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T ! [:])
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PrimaryExpr
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl PrimaryExpr
+{
+    pub fn ident_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![ident])
+    }
+
+    pub fn this_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![this])
+    }
+
+    pub fn inner(&self) -> Option<PrimaryExprInner>
+    {
+        support::child(&self.syntax)
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IfExpr
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl IfExpr
+{
+    pub fn if_branch(&self) -> Option<IfBranch> { support::child(&self.syntax) }
+
+    pub fn else_if_branchs(&self) -> AstChildren<ElseIfBranch>
     {
         support::children(&self.syntax)
     }
@@ -367,6 +831,25 @@ impl IfExpr
     }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MatchExpr
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl MatchExpr
+{
+    pub fn match_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![match])
+    }
+
+    pub fn expr(&self) -> Option<Expr> { support::child(&self.syntax) }
+
+    pub fn match_arm_list(&self) -> Option<MatchArmList>
+    {
+        support::child(&self.syntax)
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct BreakStmt
 {
     pub(crate) syntax: SyntaxNode,
@@ -438,25 +921,6 @@ impl FnCallExpr
     }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct FnCallArgs
-{
-    pub(crate) syntax: SyntaxNode,
-}
-impl FnCallArgs
-{
-    pub fn l_paren_token(&self) -> Option<SyntaxToken>
-    {
-        support::token(&self.syntax, T!['('])
-    }
-
-    pub fn exprs(&self) -> AstChildren<Expr> { support::children(&self.syntax) }
-
-    pub fn r_paren_token(&self) -> Option<SyntaxToken>
-    {
-        support::token(&self.syntax, T![')'])
-    }
-}
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct MethodCallExpr
 {
     pub(crate) syntax: SyntaxNode,
@@ -542,11 +1006,25 @@ impl MemberAccessExpr
     }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct IfBranch
+pub struct LambdaExpr
 {
     pub(crate) syntax: SyntaxNode,
 }
-impl IfBranch
+impl LambdaExpr
+{
+    pub fn param_list(&self) -> Option<ParamList>
+    {
+        support::child(&self.syntax)
+    }
+
+    pub fn body(&self) -> Option<Expr> { support::child(&self.syntax) }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IfBranch
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl IfBranch
 {
     pub fn if_token(&self) -> Option<SyntaxToken>
     {
@@ -593,48 +1071,653 @@ impl ElseBranch
     pub fn block(&self) -> Option<Block> { support::child(&self.syntax) }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum Stmt
+pub struct MatchArmList
 {
-    ExprStmt(ExprStmt),
-    DeclarationStmt(DeclarationStmt),
-    WhileStmt(WhileStmt),
-    ForStmt(ForStmt),
+    pub(crate) syntax: SyntaxNode,
+}
+impl MatchArmList
+{
+    pub fn l_curly_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T!['{'])
+    }
+
+    pub fn match_arms(&self) -> AstChildren<MatchArm>
+    {
+        support::children(&self.syntax)
+    }
+
+    pub fn r_curly_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T!['}'])
+    }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum LitValInner
+pub struct MatchArm
 {
-    StringLit(StringLit),
-    NumberLit(NumberLit),
-    BooleanLit(BooleanLit),
+    pub(crate) syntax: SyntaxNode,
+}
+impl MatchArm
+{
+    pub fn pat(&self) -> Option<Pat> { support::child(&self.syntax) }
+
+    pub fn fat_arrow_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T ! [=>])
+    }
+
+    pub fn expr(&self) -> Option<Expr> { support::child(&self.syntax) }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IdentPat
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl IdentPat
+{
+    pub fn ident_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![ident])
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TuplePat
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl TuplePat
+{
+    pub fn l_paren_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T!['('])
+    }
+
+    pub fn pats(&self) -> AstChildren<Pat> { support::children(&self.syntax) }
+
+    pub fn r_paren_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![')'])
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WildcardPat
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl WildcardPat
+{
+    pub fn underscore_token(&self) -> Option<SyntaxToken>
+    {
+        support::token(&self.syntax, T![_])
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LitPat
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl LitPat
+{
+    pub fn lit_val(&self) -> Option<LitVal> { support::child(&self.syntax) }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PathPat
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl PathPat
+{
+    pub fn path(&self) -> Option<Path> { support::child(&self.syntax) }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Expr
 {
     PrimaryExpr(PrimaryExpr),
     BinExpr(BinExpr),
+    RangeExpr(RangeExpr),
+    PipeExpr(PipeExpr),
+    TernaryExpr(TernaryExpr),
     PrefixUnaryExpr(PrefixUnaryExpr),
     FnCallExpr(FnCallExpr),
     IndexExpr(IndexExpr),
     MemberAccessExpr(MemberAccessExpr),
     MethodCallExpr(MethodCallExpr),
     FnDef(FnDef),
+    LambdaExpr(LambdaExpr),
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LitValInner
+{
+    StringLit(StringLit),
+    NumberLit(NumberLit),
+    CharLit(CharLit),
+    BooleanLit(BooleanLit),
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum StmtInner
+{
+    ExprStmt(ExprStmt),
+    DeclarationStmt(DeclarationStmt),
+    WhileStmt(WhileStmt),
+    ForStmt(ForStmt),
+    StructDef(StructDef),
+    EnumDef(EnumDef),
+}
+impl ast::HasAttrs for StmtInner {}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PrimaryExprInner
+{
+    TupleExpr(TupleExpr),
+    ArrExpr(ArrExpr),
+    MapExpr(MapExpr),
+    Block(Block),
+    Path(Path),
+    LitVal(LitVal),
+    InterpolatedStr(InterpolatedStr),
+    IfExpr(IfExpr),
+    MatchExpr(MatchExpr),
+    BreakStmt(BreakStmt),
+    ContinueStmt(ContinueStmt),
+    ReturnStmt(ReturnStmt),
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Pat
+{
+    IdentPat(IdentPat),
+    TuplePat(TuplePat),
+    WildcardPat(WildcardPat),
+    LitPat(LitPat),
+    PathPat(PathPat),
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AnyHasAttrs
+{
+    pub(crate) syntax: SyntaxNode,
+}
+impl ast::HasAttrs for AnyHasAttrs {}
+impl AstNode for Root
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == ROOT }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for Pragma
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == PRAGMA }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for ImportStmt
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == IMPORT_STMT }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for Stmt
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == STMT }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for FnCallArgs
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == FN_CALL_ARGS }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for ImportTarget
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == IMPORT_TARGET }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for StringLit
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == STRING_LIT }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for Name
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == NAME }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for InterpolatedStr
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == INTERPOLATED_STR }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for NumberLit
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == NUMBER_LIT }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for CharLit
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == CHAR_LIT }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for BooleanLit
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == BOOLEAN_LIT }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for LitVal
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == LIT_VAL }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for Attr
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == ATTR }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for FnDef
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == FN_DEF }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for ParamList
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == PARAM_LIST }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for RetType
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == RET_TYPE }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for Block
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == BLOCK }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for Param
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == PARAM }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for TypeRef
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == TYPE_REF }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for StructDef
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == STRUCT_DEF }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for FieldList
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == FIELD_LIST }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for FieldDef
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == FIELD_DEF }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for EnumDef
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == ENUM_DEF }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for VariantList
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == VARIANT_LIST }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for Variant
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == VARIANT }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for Path
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == PATH }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for GenericArgList
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == GENERIC_ARG_LIST }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl AstNode for ExprStmt
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == EXPR_STMT }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum PrimaryExprInner
+impl AstNode for DeclarationStmt
 {
-    TupleExpr(TupleExpr),
-    ArrExpr(ArrExpr),
-    Block(Block),
-    Path(Path),
-    LitVal(LitVal),
-    IfExpr(IfExpr),
-    BreakStmt(BreakStmt),
-    ContinueStmt(ContinueStmt),
-    ReturnStmt(ReturnStmt),
+    fn can_cast(kind: SyntaxKind) -> bool { kind == DECLARATION_STMT }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for Root
+impl AstNode for WhileStmt
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == ROOT }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == WHILE_STMT }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -648,9 +1731,9 @@ impl AstNode for Root
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for ImportStmt
+impl AstNode for ForStmt
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == IMPORT_STMT }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == FOR_STMT }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -664,9 +1747,9 @@ impl AstNode for ImportStmt
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for ImportTarget
+impl AstNode for BinExpr
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == IMPORT_TARGET }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == BIN_EXPR }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -680,9 +1763,9 @@ impl AstNode for ImportTarget
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for StringLit
+impl AstNode for RangeExpr
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == STRING_LIT }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == RANGE_EXPR }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -696,9 +1779,9 @@ impl AstNode for StringLit
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for Name
+impl AstNode for PipeExpr
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == NAME }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == PIPE_EXPR }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -712,9 +1795,9 @@ impl AstNode for Name
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for NumberLit
+impl AstNode for TernaryExpr
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == NUMBER_LIT }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == TERNARY_EXPR }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -728,9 +1811,9 @@ impl AstNode for NumberLit
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for BooleanLit
+impl AstNode for TupleExpr
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == BOOLEAN_LIT }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == TUPLE_EXPR }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -744,9 +1827,9 @@ impl AstNode for BooleanLit
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for LitVal
+impl AstNode for ArrExpr
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == LIT_VAL }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == ARR_EXPR }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -760,9 +1843,9 @@ impl AstNode for LitVal
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for FnDef
+impl AstNode for MapExpr
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == FN_DEF }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == MAP_EXPR }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -776,9 +1859,9 @@ impl AstNode for FnDef
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for Block
+impl AstNode for MapEntry
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == BLOCK }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == MAP_ENTRY }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -792,9 +1875,9 @@ impl AstNode for Block
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for ExprStmt
+impl AstNode for PrimaryExpr
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == EXPR_STMT }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == PRIMARY_EXPR }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -808,9 +1891,9 @@ impl AstNode for ExprStmt
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for DeclarationStmt
+impl AstNode for IfExpr
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == DECLARATION_STMT }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == IF_EXPR }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -824,9 +1907,9 @@ impl AstNode for DeclarationStmt
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for WhileStmt
+impl AstNode for MatchExpr
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == WHILE_STMT }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == MATCH_EXPR }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -840,9 +1923,9 @@ impl AstNode for WhileStmt
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for ForStmt
+impl AstNode for BreakStmt
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == FOR_STMT }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == BREAK_STMT }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -856,9 +1939,9 @@ impl AstNode for ForStmt
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for BinExpr
+impl AstNode for ContinueStmt
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == BIN_EXPR }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == CONTINUE_STMT }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -872,9 +1955,9 @@ impl AstNode for BinExpr
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for TupleExpr
+impl AstNode for ReturnStmt
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == TUPLE_EXPR }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == RETURN_STMT }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -888,9 +1971,9 @@ impl AstNode for TupleExpr
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for ArrExpr
+impl AstNode for PathFrag
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == ARR_EXPR }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == PATH_FRAG }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -904,9 +1987,9 @@ impl AstNode for ArrExpr
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for PrimaryExpr
+impl AstNode for FnCallExpr
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == PRIMARY_EXPR }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == FN_CALL_EXPR }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -920,9 +2003,9 @@ impl AstNode for PrimaryExpr
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for Path
+impl AstNode for MethodCallExpr
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == PATH }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == METHOD_CALL_EXPR }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -936,9 +2019,9 @@ impl AstNode for Path
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for IfExpr
+impl AstNode for PrefixUnaryExpr
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == IF_EXPR }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == PREFIX_UNARY_EXPR }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -952,9 +2035,9 @@ impl AstNode for IfExpr
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for BreakStmt
+impl AstNode for IndexExpr
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == BREAK_STMT }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == INDEX_EXPR }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -968,9 +2051,9 @@ impl AstNode for BreakStmt
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for ContinueStmt
+impl AstNode for IndexExprBrackets
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == CONTINUE_STMT }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == INDEX_EXPR_BRACKETS }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -984,9 +2067,9 @@ impl AstNode for ContinueStmt
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for ReturnStmt
+impl AstNode for MemberAccessExpr
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == RETURN_STMT }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == MEMBER_ACCESS_EXPR }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -1000,9 +2083,9 @@ impl AstNode for ReturnStmt
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for PathFrag
+impl AstNode for LambdaExpr
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == PATH_FRAG }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == LAMBDA_EXPR }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -1016,9 +2099,9 @@ impl AstNode for PathFrag
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for FnCallExpr
+impl AstNode for IfBranch
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == FN_CALL_EXPR }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == IF_BRANCH }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -1032,9 +2115,9 @@ impl AstNode for FnCallExpr
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for FnCallArgs
+impl AstNode for ElseIfBranch
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == FN_CALL_ARGS }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == ELSE_IF_BRANCH }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -1048,9 +2131,9 @@ impl AstNode for FnCallArgs
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for MethodCallExpr
+impl AstNode for ElseBranch
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == METHOD_CALL_EXPR }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == ELSE_BRANCH }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -1064,9 +2147,9 @@ impl AstNode for MethodCallExpr
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for PrefixUnaryExpr
+impl AstNode for MatchArmList
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == PREFIX_UNARY_EXPR }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == MATCH_ARM_LIST }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -1080,9 +2163,9 @@ impl AstNode for PrefixUnaryExpr
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for IndexExpr
+impl AstNode for MatchArm
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == INDEX_EXPR }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == MATCH_ARM }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -1096,9 +2179,9 @@ impl AstNode for IndexExpr
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for IndexExprBrackets
+impl AstNode for IdentPat
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == INDEX_EXPR_BRACKETS }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == IDENT_PAT }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -1112,9 +2195,9 @@ impl AstNode for IndexExprBrackets
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for MemberAccessExpr
+impl AstNode for TuplePat
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == MEMBER_ACCESS_EXPR }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == TUPLE_PAT }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -1128,9 +2211,9 @@ impl AstNode for MemberAccessExpr
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for IfBranch
+impl AstNode for WildcardPat
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == IF_BRANCH }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == WILDCARD_PAT }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -1144,9 +2227,9 @@ impl AstNode for IfBranch
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for ElseIfBranch
+impl AstNode for LitPat
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == ELSE_IF_BRANCH }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == LIT_PAT }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -1160,9 +2243,9 @@ impl AstNode for ElseIfBranch
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl AstNode for ElseBranch
+impl AstNode for PathPat
 {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == ELSE_BRANCH }
+    fn can_cast(kind: SyntaxKind) -> bool { kind == PATH_PAT }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
@@ -1176,41 +2259,92 @@ impl AstNode for ElseBranch
 
     fn syntax(&self) -> &SyntaxNode { &self.syntax }
 }
-impl From<ExprStmt> for Stmt
+impl From<PrimaryExpr> for Expr
+{
+    fn from(node: PrimaryExpr) -> Expr { Expr::PrimaryExpr(node) }
+}
+impl From<BinExpr> for Expr
 {
-    fn from(node: ExprStmt) -> Stmt { Stmt::ExprStmt(node) }
+    fn from(node: BinExpr) -> Expr { Expr::BinExpr(node) }
 }
-impl From<DeclarationStmt> for Stmt
+impl From<RangeExpr> for Expr
 {
-    fn from(node: DeclarationStmt) -> Stmt { Stmt::DeclarationStmt(node) }
+    fn from(node: RangeExpr) -> Expr { Expr::RangeExpr(node) }
 }
-impl From<WhileStmt> for Stmt
+impl From<PipeExpr> for Expr
 {
-    fn from(node: WhileStmt) -> Stmt { Stmt::WhileStmt(node) }
+    fn from(node: PipeExpr) -> Expr { Expr::PipeExpr(node) }
 }
-impl From<ForStmt> for Stmt
+impl From<TernaryExpr> for Expr
 {
-    fn from(node: ForStmt) -> Stmt { Stmt::ForStmt(node) }
+    fn from(node: TernaryExpr) -> Expr { Expr::TernaryExpr(node) }
 }
-impl AstNode for Stmt
+impl From<PrefixUnaryExpr> for Expr
+{
+    fn from(node: PrefixUnaryExpr) -> Expr { Expr::PrefixUnaryExpr(node) }
+}
+impl From<FnCallExpr> for Expr
+{
+    fn from(node: FnCallExpr) -> Expr { Expr::FnCallExpr(node) }
+}
+impl From<IndexExpr> for Expr
+{
+    fn from(node: IndexExpr) -> Expr { Expr::IndexExpr(node) }
+}
+impl From<MemberAccessExpr> for Expr
+{
+    fn from(node: MemberAccessExpr) -> Expr { Expr::MemberAccessExpr(node) }
+}
+impl From<MethodCallExpr> for Expr
+{
+    fn from(node: MethodCallExpr) -> Expr { Expr::MethodCallExpr(node) }
+}
+impl From<FnDef> for Expr
+{
+    fn from(node: FnDef) -> Expr { Expr::FnDef(node) }
+}
+impl From<LambdaExpr> for Expr
+{
+    fn from(node: LambdaExpr) -> Expr { Expr::LambdaExpr(node) }
+}
+impl AstNode for Expr
 {
     fn can_cast(kind: SyntaxKind) -> bool
     {
-        match kind {
-            EXPR_STMT | DECLARATION_STMT | WHILE_STMT | FOR_STMT => true,
-            _ => false,
-        }
+        matches!(kind,
+                 PRIMARY_EXPR
+                 | BIN_EXPR
+                 | RANGE_EXPR
+                 | PIPE_EXPR
+                 | TERNARY_EXPR
+                 | PREFIX_UNARY_EXPR
+                 | FN_CALL_EXPR
+                 | INDEX_EXPR
+                 | MEMBER_ACCESS_EXPR
+                 | METHOD_CALL_EXPR
+                 | FN_DEF
+                 | LAMBDA_EXPR)
     }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
         let res = match syntax.kind() {
-            EXPR_STMT => Stmt::ExprStmt(ExprStmt { syntax }),
-            DECLARATION_STMT => {
-                Stmt::DeclarationStmt(DeclarationStmt { syntax })
+            PRIMARY_EXPR => Expr::PrimaryExpr(PrimaryExpr { syntax }),
+            BIN_EXPR => Expr::BinExpr(BinExpr { syntax }),
+            RANGE_EXPR => Expr::RangeExpr(RangeExpr { syntax }),
+            PIPE_EXPR => Expr::PipeExpr(PipeExpr { syntax }),
+            TERNARY_EXPR => Expr::TernaryExpr(TernaryExpr { syntax }),
+            PREFIX_UNARY_EXPR => {
+                Expr::PrefixUnaryExpr(PrefixUnaryExpr { syntax })
+            }
+            FN_CALL_EXPR => Expr::FnCallExpr(FnCallExpr { syntax }),
+            INDEX_EXPR => Expr::IndexExpr(IndexExpr { syntax }),
+            MEMBER_ACCESS_EXPR => {
+                Expr::MemberAccessExpr(MemberAccessExpr { syntax })
             }
-            WHILE_STMT => Stmt::WhileStmt(WhileStmt { syntax }),
-            FOR_STMT => Stmt::ForStmt(ForStmt { syntax }),
+            METHOD_CALL_EXPR => Expr::MethodCallExpr(MethodCallExpr { syntax }),
+            FN_DEF => Expr::FnDef(FnDef { syntax }),
+            LAMBDA_EXPR => Expr::LambdaExpr(LambdaExpr { syntax }),
             _ => return None,
         };
         Some(res)
@@ -1219,10 +2353,18 @@ impl AstNode for Stmt
     fn syntax(&self) -> &SyntaxNode
     {
         match self {
-            Stmt::ExprStmt(it) => &it.syntax,
-            Stmt::DeclarationStmt(it) => &it.syntax,
-            Stmt::WhileStmt(it) => &it.syntax,
-            Stmt::ForStmt(it) => &it.syntax,
+            Expr::PrimaryExpr(it) => &it.syntax,
+            Expr::BinExpr(it) => &it.syntax,
+            Expr::RangeExpr(it) => &it.syntax,
+            Expr::PipeExpr(it) => &it.syntax,
+            Expr::TernaryExpr(it) => &it.syntax,
+            Expr::PrefixUnaryExpr(it) => &it.syntax,
+            Expr::FnCallExpr(it) => &it.syntax,
+            Expr::IndexExpr(it) => &it.syntax,
+            Expr::MemberAccessExpr(it) => &it.syntax,
+            Expr::MethodCallExpr(it) => &it.syntax,
+            Expr::FnDef(it) => &it.syntax,
+            Expr::LambdaExpr(it) => &it.syntax,
         }
     }
 }
@@ -1234,6 +2376,10 @@ impl From<NumberLit> for LitValInner
 {
     fn from(node: NumberLit) -> LitValInner { LitValInner::NumberLit(node) }
 }
+impl From<CharLit> for LitValInner
+{
+    fn from(node: CharLit) -> LitValInner { LitValInner::CharLit(node) }
+}
 impl From<BooleanLit> for LitValInner
 {
     fn from(node: BooleanLit) -> LitValInner { LitValInner::BooleanLit(node) }
@@ -1242,10 +2388,8 @@ impl AstNode for LitValInner
 {
     fn can_cast(kind: SyntaxKind) -> bool
     {
-        match kind {
-            STRING_LIT | NUMBER_LIT | BOOLEAN_LIT => true,
-            _ => false,
-        }
+        matches!(kind,
+                 STRING_LIT | NUMBER_LIT | CHAR_LIT | BOOLEAN_LIT)
     }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
@@ -1253,6 +2397,7 @@ impl AstNode for LitValInner
         let res = match syntax.kind() {
             STRING_LIT => LitValInner::StringLit(StringLit { syntax }),
             NUMBER_LIT => LitValInner::NumberLit(NumberLit { syntax }),
+            CHAR_LIT => LitValInner::CharLit(CharLit { syntax }),
             BOOLEAN_LIT => LitValInner::BooleanLit(BooleanLit { syntax }),
             _ => return None,
         };
@@ -1264,70 +2409,62 @@ impl AstNode for LitValInner
         match self {
             LitValInner::StringLit(it) => &it.syntax,
             LitValInner::NumberLit(it) => &it.syntax,
+            LitValInner::CharLit(it) => &it.syntax,
             LitValInner::BooleanLit(it) => &it.syntax,
         }
     }
 }
-impl From<PrimaryExpr> for Expr
-{
-    fn from(node: PrimaryExpr) -> Expr { Expr::PrimaryExpr(node) }
-}
-impl From<BinExpr> for Expr
-{
-    fn from(node: BinExpr) -> Expr { Expr::BinExpr(node) }
-}
-impl From<PrefixUnaryExpr> for Expr
+impl From<ExprStmt> for StmtInner
 {
-    fn from(node: PrefixUnaryExpr) -> Expr { Expr::PrefixUnaryExpr(node) }
+    fn from(node: ExprStmt) -> StmtInner { StmtInner::ExprStmt(node) }
 }
-impl From<FnCallExpr> for Expr
+impl From<DeclarationStmt> for StmtInner
 {
-    fn from(node: FnCallExpr) -> Expr { Expr::FnCallExpr(node) }
+    fn from(node: DeclarationStmt) -> StmtInner
+    {
+        StmtInner::DeclarationStmt(node)
+    }
 }
-impl From<IndexExpr> for Expr
+impl From<WhileStmt> for StmtInner
 {
-    fn from(node: IndexExpr) -> Expr { Expr::IndexExpr(node) }
+    fn from(node: WhileStmt) -> StmtInner { StmtInner::WhileStmt(node) }
 }
-impl From<MemberAccessExpr> for Expr
+impl From<ForStmt> for StmtInner
 {
-    fn from(node: MemberAccessExpr) -> Expr { Expr::MemberAccessExpr(node) }
+    fn from(node: ForStmt) -> StmtInner { StmtInner::ForStmt(node) }
 }
-impl From<MethodCallExpr> for Expr
+impl From<StructDef> for StmtInner
 {
-    fn from(node: MethodCallExpr) -> Expr { Expr::MethodCallExpr(node) }
+    fn from(node: StructDef) -> StmtInner { StmtInner::StructDef(node) }
 }
-impl From<FnDef> for Expr
+impl From<EnumDef> for StmtInner
 {
-    fn from(node: FnDef) -> Expr { Expr::FnDef(node) }
+    fn from(node: EnumDef) -> StmtInner { StmtInner::EnumDef(node) }
 }
-impl AstNode for Expr
+impl AstNode for StmtInner
 {
     fn can_cast(kind: SyntaxKind) -> bool
     {
-        match kind {
-            PRIMARY_EXPR | BIN_EXPR | PREFIX_UNARY_EXPR | FN_CALL_EXPR
-            | INDEX_EXPR | MEMBER_ACCESS_EXPR | METHOD_CALL_EXPR | FN_DEF => {
-                true
-            }
-            _ => false,
-        }
+        matches!(kind,
+                 EXPR_STMT
+                 | DECLARATION_STMT
+                 | WHILE_STMT
+                 | FOR_STMT
+                 | STRUCT_DEF
+                 | ENUM_DEF)
     }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
     {
         let res = match syntax.kind() {
-            PRIMARY_EXPR => Expr::PrimaryExpr(PrimaryExpr { syntax }),
-            BIN_EXPR => Expr::BinExpr(BinExpr { syntax }),
-            PREFIX_UNARY_EXPR => {
-                Expr::PrefixUnaryExpr(PrefixUnaryExpr { syntax })
-            }
-            FN_CALL_EXPR => Expr::FnCallExpr(FnCallExpr { syntax }),
-            INDEX_EXPR => Expr::IndexExpr(IndexExpr { syntax }),
-            MEMBER_ACCESS_EXPR => {
-                Expr::MemberAccessExpr(MemberAccessExpr { syntax })
+            EXPR_STMT => StmtInner::ExprStmt(ExprStmt { syntax }),
+            DECLARATION_STMT => {
+                StmtInner::DeclarationStmt(DeclarationStmt { syntax })
             }
-            METHOD_CALL_EXPR => Expr::MethodCallExpr(MethodCallExpr { syntax }),
-            FN_DEF => Expr::FnDef(FnDef { syntax }),
+            WHILE_STMT => StmtInner::WhileStmt(WhileStmt { syntax }),
+            FOR_STMT => StmtInner::ForStmt(ForStmt { syntax }),
+            STRUCT_DEF => StmtInner::StructDef(StructDef { syntax }),
+            ENUM_DEF => StmtInner::EnumDef(EnumDef { syntax }),
             _ => return None,
         };
         Some(res)
@@ -1336,14 +2473,12 @@ impl AstNode for Expr
     fn syntax(&self) -> &SyntaxNode
     {
         match self {
-            Expr::PrimaryExpr(it) => &it.syntax,
-            Expr::BinExpr(it) => &it.syntax,
-            Expr::PrefixUnaryExpr(it) => &it.syntax,
-            Expr::FnCallExpr(it) => &it.syntax,
-            Expr::IndexExpr(it) => &it.syntax,
-            Expr::MemberAccessExpr(it) => &it.syntax,
-            Expr::MethodCallExpr(it) => &it.syntax,
-            Expr::FnDef(it) => &it.syntax,
+            StmtInner::ExprStmt(it) => &it.syntax,
+            StmtInner::DeclarationStmt(it) => &it.syntax,
+            StmtInner::WhileStmt(it) => &it.syntax,
+            StmtInner::ForStmt(it) => &it.syntax,
+            StmtInner::StructDef(it) => &it.syntax,
+            StmtInner::EnumDef(it) => &it.syntax,
         }
     }
 }
@@ -1361,6 +2496,13 @@ impl From<ArrExpr> for PrimaryExprInner
         PrimaryExprInner::ArrExpr(node)
     }
 }
+impl From<MapExpr> for PrimaryExprInner
+{
+    fn from(node: MapExpr) -> PrimaryExprInner
+    {
+        PrimaryExprInner::MapExpr(node)
+    }
+}
 impl From<Block> for PrimaryExprInner
 {
     fn from(node: Block) -> PrimaryExprInner { PrimaryExprInner::Block(node) }
@@ -1373,10 +2515,24 @@ impl From<LitVal> for PrimaryExprInner
 {
     fn from(node: LitVal) -> PrimaryExprInner { PrimaryExprInner::LitVal(node) }
 }
+impl From<InterpolatedStr> for PrimaryExprInner
+{
+    fn from(node: InterpolatedStr) -> PrimaryExprInner
+    {
+        PrimaryExprInner::InterpolatedStr(node)
+    }
+}
 impl From<IfExpr> for PrimaryExprInner
 {
     fn from(node: IfExpr) -> PrimaryExprInner { PrimaryExprInner::IfExpr(node) }
 }
+impl From<MatchExpr> for PrimaryExprInner
+{
+    fn from(node: MatchExpr) -> PrimaryExprInner
+    {
+        PrimaryExprInner::MatchExpr(node)
+    }
+}
 impl From<BreakStmt> for PrimaryExprInner
 {
     fn from(node: BreakStmt) -> PrimaryExprInner
@@ -1402,11 +2558,19 @@ impl AstNode for PrimaryExprInner
 {
     fn can_cast(kind: SyntaxKind) -> bool
     {
-        match kind {
-            TUPLE_EXPR | ARR_EXPR | BLOCK | PATH | LIT_VAL | IF_EXPR
-            | BREAK_STMT | CONTINUE_STMT | RETURN_STMT => true,
-            _ => false,
-        }
+        matches!(kind,
+                 TUPLE_EXPR
+                 | ARR_EXPR
+                 | MAP_EXPR
+                 | BLOCK
+                 | PATH
+                 | LIT_VAL
+                 | INTERPOLATED_STR
+                 | IF_EXPR
+                 | MATCH_EXPR
+                 | BREAK_STMT
+                 | CONTINUE_STMT
+                 | RETURN_STMT)
     }
 
     fn cast(syntax: SyntaxNode) -> Option<Self>
@@ -1414,10 +2578,15 @@ impl AstNode for PrimaryExprInner
         let res = match syntax.kind() {
             TUPLE_EXPR => PrimaryExprInner::TupleExpr(TupleExpr { syntax }),
             ARR_EXPR => PrimaryExprInner::ArrExpr(ArrExpr { syntax }),
+            MAP_EXPR => PrimaryExprInner::MapExpr(MapExpr { syntax }),
             BLOCK => PrimaryExprInner::Block(Block { syntax }),
             PATH => PrimaryExprInner::Path(Path { syntax }),
             LIT_VAL => PrimaryExprInner::LitVal(LitVal { syntax }),
+            INTERPOLATED_STR => {
+                PrimaryExprInner::InterpolatedStr(InterpolatedStr { syntax })
+            }
             IF_EXPR => PrimaryExprInner::IfExpr(IfExpr { syntax }),
+            MATCH_EXPR => PrimaryExprInner::MatchExpr(MatchExpr { syntax }),
             BREAK_STMT => PrimaryExprInner::BreakStmt(BreakStmt { syntax }),
             CONTINUE_STMT => {
                 PrimaryExprInner::ContinueStmt(ContinueStmt { syntax })
@@ -1433,17 +2602,100 @@ impl AstNode for PrimaryExprInner
         match self {
             PrimaryExprInner::TupleExpr(it) => &it.syntax,
             PrimaryExprInner::ArrExpr(it) => &it.syntax,
+            PrimaryExprInner::MapExpr(it) => &it.syntax,
             PrimaryExprInner::Block(it) => &it.syntax,
             PrimaryExprInner::Path(it) => &it.syntax,
             PrimaryExprInner::LitVal(it) => &it.syntax,
+            PrimaryExprInner::InterpolatedStr(it) => &it.syntax,
             PrimaryExprInner::IfExpr(it) => &it.syntax,
+            PrimaryExprInner::MatchExpr(it) => &it.syntax,
             PrimaryExprInner::BreakStmt(it) => &it.syntax,
             PrimaryExprInner::ContinueStmt(it) => &it.syntax,
             PrimaryExprInner::ReturnStmt(it) => &it.syntax,
         }
     }
 }
-impl std::fmt::Display for Stmt
+impl From<IdentPat> for Pat
+{
+    fn from(node: IdentPat) -> Pat { Pat::IdentPat(node) }
+}
+impl From<TuplePat> for Pat
+{
+    fn from(node: TuplePat) -> Pat { Pat::TuplePat(node) }
+}
+impl From<WildcardPat> for Pat
+{
+    fn from(node: WildcardPat) -> Pat { Pat::WildcardPat(node) }
+}
+impl From<LitPat> for Pat
+{
+    fn from(node: LitPat) -> Pat { Pat::LitPat(node) }
+}
+impl From<PathPat> for Pat
+{
+    fn from(node: PathPat) -> Pat { Pat::PathPat(node) }
+}
+impl AstNode for Pat
+{
+    fn can_cast(kind: SyntaxKind) -> bool
+    {
+        matches!(kind,
+                 IDENT_PAT | TUPLE_PAT | WILDCARD_PAT | LIT_PAT | PATH_PAT)
+    }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        let res = match syntax.kind() {
+            IDENT_PAT => Pat::IdentPat(IdentPat { syntax }),
+            TUPLE_PAT => Pat::TuplePat(TuplePat { syntax }),
+            WILDCARD_PAT => Pat::WildcardPat(WildcardPat { syntax }),
+            LIT_PAT => Pat::LitPat(LitPat { syntax }),
+            PATH_PAT => Pat::PathPat(PathPat { syntax }),
+            _ => return None,
+        };
+        Some(res)
+    }
+
+    fn syntax(&self) -> &SyntaxNode
+    {
+        match self {
+            Pat::IdentPat(it) => &it.syntax,
+            Pat::TuplePat(it) => &it.syntax,
+            Pat::WildcardPat(it) => &it.syntax,
+            Pat::LitPat(it) => &it.syntax,
+            Pat::PathPat(it) => &it.syntax,
+        }
+    }
+}
+impl AnyHasAttrs
+{
+    #[inline]
+    pub fn new<T: ast::HasAttrs>(node: T) -> AnyHasAttrs
+    {
+        AnyHasAttrs { syntax: node.syntax().clone(), }
+    }
+}
+impl AstNode for AnyHasAttrs
+{
+    fn can_cast(kind: SyntaxKind) -> bool
+    {
+        matches!(kind,
+                 STRUCT_DEF
+                 | ENUM_DEF
+                 | EXPR_STMT
+                 | DECLARATION_STMT
+                 | WHILE_STMT
+                 | FOR_STMT)
+    }
+
+    fn cast(syntax: SyntaxNode) -> Option<Self>
+    {
+        Self::can_cast(syntax.kind()).then(|| AnyHasAttrs { syntax })
+    }
+
+    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+}
+impl std::fmt::Display for Expr
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
     {
@@ -1457,7 +2709,7 @@ impl std::fmt::Display for LitValInner
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
-impl std::fmt::Display for Expr
+impl std::fmt::Display for StmtInner
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
     {
@@ -1471,6 +2723,13 @@ impl std::fmt::Display for PrimaryExprInner
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
+impl std::fmt::Display for Pat
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
 impl std::fmt::Display for Root
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
@@ -1478,6 +2737,13 @@ impl std::fmt::Display for Root
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
+impl std::fmt::Display for Pragma
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
 impl std::fmt::Display for ImportStmt
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
@@ -1485,6 +2751,20 @@ impl std::fmt::Display for ImportStmt
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
+impl std::fmt::Display for Stmt
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
+impl std::fmt::Display for FnCallArgs
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
 impl std::fmt::Display for ImportTarget
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
@@ -1506,6 +2786,13 @@ impl std::fmt::Display for Name
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
+impl std::fmt::Display for InterpolatedStr
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
 impl std::fmt::Display for NumberLit
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
@@ -1513,6 +2800,13 @@ impl std::fmt::Display for NumberLit
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
+impl std::fmt::Display for CharLit
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
 impl std::fmt::Display for BooleanLit
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
@@ -1527,6 +2821,13 @@ impl std::fmt::Display for LitVal
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
+impl std::fmt::Display for Attr
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
 impl std::fmt::Display for FnDef
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
@@ -1534,6 +2835,20 @@ impl std::fmt::Display for FnDef
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
+impl std::fmt::Display for ParamList
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
+impl std::fmt::Display for RetType
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
 impl std::fmt::Display for Block
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
@@ -1541,6 +2856,76 @@ impl std::fmt::Display for Block
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
+impl std::fmt::Display for Param
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
+impl std::fmt::Display for TypeRef
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
+impl std::fmt::Display for StructDef
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
+impl std::fmt::Display for FieldList
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
+impl std::fmt::Display for FieldDef
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
+impl std::fmt::Display for EnumDef
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
+impl std::fmt::Display for VariantList
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
+impl std::fmt::Display for Variant
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
+impl std::fmt::Display for Path
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
+impl std::fmt::Display for GenericArgList
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
 impl std::fmt::Display for ExprStmt
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
@@ -1576,6 +2961,27 @@ impl std::fmt::Display for BinExpr
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
+impl std::fmt::Display for RangeExpr
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
+impl std::fmt::Display for PipeExpr
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
+impl std::fmt::Display for TernaryExpr
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
 impl std::fmt::Display for TupleExpr
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
@@ -1590,14 +2996,21 @@ impl std::fmt::Display for ArrExpr
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
-impl std::fmt::Display for PrimaryExpr
+impl std::fmt::Display for MapExpr
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
     {
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
-impl std::fmt::Display for Path
+impl std::fmt::Display for MapEntry
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
+impl std::fmt::Display for PrimaryExpr
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
     {
@@ -1611,42 +3024,42 @@ impl std::fmt::Display for IfExpr
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
-impl std::fmt::Display for BreakStmt
+impl std::fmt::Display for MatchExpr
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
     {
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
-impl std::fmt::Display for ContinueStmt
+impl std::fmt::Display for BreakStmt
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
     {
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
-impl std::fmt::Display for ReturnStmt
+impl std::fmt::Display for ContinueStmt
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
     {
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
-impl std::fmt::Display for PathFrag
+impl std::fmt::Display for ReturnStmt
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
     {
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
-impl std::fmt::Display for FnCallExpr
+impl std::fmt::Display for PathFrag
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
     {
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
-impl std::fmt::Display for FnCallArgs
+impl std::fmt::Display for FnCallExpr
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
     {
@@ -1688,6 +3101,13 @@ impl std::fmt::Display for MemberAccessExpr
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
+impl std::fmt::Display for LambdaExpr
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
 impl std::fmt::Display for IfBranch
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
@@ -1709,3 +3129,52 @@ impl std::fmt::Display for ElseBranch
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
+impl std::fmt::Display for MatchArmList
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
+impl std::fmt::Display for MatchArm
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
+impl std::fmt::Display for IdentPat
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
+impl std::fmt::Display for TuplePat
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
+impl std::fmt::Display for WildcardPat
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
+impl std::fmt::Display for LitPat
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
+impl std::fmt::Display for PathPat
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
@@ -1,9 +1,9 @@
-use crate::ast::AstToken;
 use crate::SyntaxKind::{
     self,
     *,
 };
 use crate::SyntaxToken;
+use crate::ast::AstToken;
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Whitespace
 {
@@ -89,6 +89,62 @@ impl AstToken for BlockComment
     fn syntax(&self) -> &SyntaxToken { &self.syntax }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DocComment
+{
+    pub(crate) syntax: SyntaxToken,
+}
+impl std::fmt::Display for DocComment
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(&self.syntax, f)
+    }
+}
+impl AstToken for DocComment
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == DOC_COMMENT }
+
+    fn cast(syntax: SyntaxToken) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlockDocComment
+{
+    pub(crate) syntax: SyntaxToken,
+}
+impl std::fmt::Display for BlockDocComment
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(&self.syntax, f)
+    }
+}
+impl AstToken for BlockDocComment
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == BLOCK_DOC_COMMENT }
+
+    fn cast(syntax: SyntaxToken) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Str
 {
     pub(crate) syntax: SyntaxToken,
@@ -145,6 +201,34 @@ impl AstToken for MultilineStr
     fn syntax(&self) -> &SyntaxToken { &self.syntax }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RawStr
+{
+    pub(crate) syntax: SyntaxToken,
+}
+impl std::fmt::Display for RawStr
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(&self.syntax, f)
+    }
+}
+impl AstToken for RawStr
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == RAW_STR }
+
+    fn cast(syntax: SyntaxToken) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct IntNumber
 {
     pub(crate) syntax: SyntaxToken,
@@ -201,6 +285,34 @@ impl AstToken for FloatNumber
     fn syntax(&self) -> &SyntaxToken { &self.syntax }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Char
+{
+    pub(crate) syntax: SyntaxToken,
+}
+impl std::fmt::Display for Char
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        std::fmt::Display::fmt(&self.syntax, f)
+    }
+}
+impl AstToken for Char
+{
+    fn can_cast(kind: SyntaxKind) -> bool { kind == CHAR }
+
+    fn cast(syntax: SyntaxToken) -> Option<Self>
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        }
+        else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Ident
 {
     pub(crate) syntax: SyntaxToken,
@@ -0,0 +1,320 @@
+//! Decoding of string and char literal values.
+
+use std::fmt;
+
+use rowan::{
+    TextRange,
+    TextSize,
+};
+
+use super::{
+    AstNode,
+    CharLit,
+    Expr,
+    InterpolatedStr,
+    StringLit,
+};
+use crate::{
+    NodeOrToken,
+    SyntaxKind,
+    SyntaxToken,
+};
+
+/// An invalid escape sequence found while decoding a string literal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EscapeError
+{
+    pub kind:  EscapeErrorKind,
+    /// The range of the offending escape, in the source file.
+    pub range: TextRange,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscapeErrorKind
+{
+    /// A `\` followed by a character that doesn't start an escape.
+    UnknownEscape,
+    /// A `\` at the very end of the literal.
+    LoneSlash,
+    /// A `\u` that isn't followed by `{`.
+    NoBraceInUnicodeEscape,
+    /// A `\u{` without the closing `}`.
+    UnclosedUnicodeEscape,
+    /// A `\u{}` with no digits, a non-hex digit or more than six digits.
+    InvalidUnicodeEscape,
+    /// A `\u{..}` that is a surrogate or above `10FFFF`.
+    OutOfRangeUnicodeEscape,
+}
+
+impl fmt::Display for EscapeErrorKind
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let msg = match self {
+            Self::UnknownEscape => "unknown character escape",
+            Self::LoneSlash => "escape at the end of the string",
+            Self::NoBraceInUnicodeEscape => "expected `{` after `\\u`",
+            Self::UnclosedUnicodeEscape => "unterminated unicode escape",
+            Self::InvalidUnicodeEscape => "invalid unicode escape",
+            Self::OutOfRangeUnicodeEscape => {
+                "unicode escape is not a valid char"
+            }
+        };
+        f.write_str(msg)
+    }
+}
+
+impl StringLit
+{
+    /// The literal's token, whichever kind of string it is.
+    pub fn token(&self) -> Option<SyntaxToken>
+    {
+        self.str_token()
+            .or_else(|| self.multiline_str_token())
+            .or_else(|| self.raw_str_token())
+    }
+
+    /// Decodes the value of the literal.
+    ///
+    /// Delimiters are stripped and escapes are resolved. Multiline strings
+    /// also lose the common indentation of their lines, as well as the first
+    /// and last line when those are blank. Raw strings are taken verbatim.
+    pub fn value(&self) -> Result<String, Vec<EscapeError>>
+    {
+        let token = match self.token() {
+            Some(token) => token,
+            None => return Ok(String::new()),
+        };
+        let text = token.text();
+        let start = token.text_range().start();
+        let mut value = String::with_capacity(text.len());
+        let mut errors = vec![];
+
+        match token.kind() {
+            SyntaxKind::STR => {
+                let body = &text[1..text.len() - 1];
+                unescape(body,
+                         start + TextSize::from(1),
+                         &mut value,
+                         &mut errors);
+            }
+            SyntaxKind::MULTILINE_STR => {
+                let body = &text[3..text.len() - 3];
+                let lines = multiline_body(body);
+                let indent = lines.iter()
+                                  .filter(|(_, line)| !line.trim().is_empty())
+                                  .map(|(_, line)| indentation(line))
+                                  .min()
+                                  .unwrap_or(0);
+                for (i, (offset, line)) in lines.iter().enumerate() {
+                    if i > 0 {
+                        value.push('\n');
+                    }
+                    let line = line.get(indent..).unwrap_or("");
+                    let offset =
+                        start + TextSize::from((3 + offset + indent) as u32);
+                    unescape(line, offset, &mut value, &mut errors);
+                }
+            }
+            SyntaxKind::RAW_STR => {
+                let hashes =
+                    text[1..].bytes().take_while(|&b| b == b'#').count();
+                value.push_str(&text[hashes + 2..text.len() - hashes - 1]);
+            }
+            _ => unreachable!(),
+        }
+
+        if errors.is_empty() {
+            Ok(value)
+        }
+        else {
+            Err(errors)
+        }
+    }
+}
+
+impl CharLit
+{
+    /// Decodes the value of the literal, resolving escapes like in a
+    /// [`StringLit`].
+    pub fn value(&self) -> Result<char, Vec<EscapeError>>
+    {
+        let token = match self.char_token() {
+            Some(token) => token,
+            None => return Ok('\0'),
+        };
+        let text = token.text();
+        let mut value = String::new();
+        let mut errors = vec![];
+        // `c'..'`
+        unescape(&text[2..text.len() - 1],
+                 token.text_range().start() + TextSize::from(2),
+                 &mut value,
+                 &mut errors);
+
+        if errors.is_empty() {
+            // the lexer only lets through a single char or escape
+            Ok(value.chars().next().unwrap())
+        }
+        else {
+            Err(errors)
+        }
+    }
+}
+
+/// A piece of an [`InterpolatedStr`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InterpolatedStrPart
+{
+    Str(String),
+    Expr(Expr),
+}
+
+impl InterpolatedStr
+{
+    /// The text and the embedded expressions of the string, in order.
+    ///
+    /// Escapes in the text are resolved like in a [`StringLit`], and the text
+    /// between two adjacent interpolations is left out when it is empty.
+    pub fn parts(&self) -> Result<Vec<InterpolatedStrPart>, Vec<EscapeError>>
+    {
+        let mut parts = vec![];
+        let mut errors = vec![];
+
+        for child in self.syntax().children_with_tokens() {
+            match child {
+                NodeOrToken::Node(node) => {
+                    let expr = Expr::cast(node);
+                    parts.extend(expr.map(InterpolatedStrPart::Expr));
+                }
+                NodeOrToken::Token(token) => {
+                    // `'..${`, `}..${` and `}..'`
+                    let (open, close) = match token.kind() {
+                        SyntaxKind::STR_START | SyntaxKind::STR_MIDDLE => {
+                            (1, 2)
+                        }
+                        SyntaxKind::STR_END => (1, 1),
+                        _ => continue,
+                    };
+                    let text = token.text();
+                    let start = token.text_range().start();
+                    let mut value = String::new();
+                    unescape(&text[open..text.len() - close],
+                             start + TextSize::from(open as u32),
+                             &mut value,
+                             &mut errors);
+                    if !value.is_empty() {
+                        parts.push(InterpolatedStrPart::Str(value));
+                    }
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(parts)
+        }
+        else {
+            Err(errors)
+        }
+    }
+}
+
+/// Splits the body of a multiline string into lines along with their offsets
+/// in the body, dropping the first and last line when they are blank.
+fn multiline_body(body: &str) -> Vec<(usize, &str)>
+{
+    let mut offset = 0;
+    let mut lines: Vec<_> =
+        body.split('\n')
+            .map(|line| {
+                let it = (offset, line.strip_suffix('\r').unwrap_or(line));
+                offset += line.len() + 1;
+                it
+            })
+            .collect();
+    if lines.len() > 1 {
+        if lines.last().is_some_and(|(_, line)| line.trim().is_empty()) {
+            lines.pop();
+        }
+        if lines[0].1.trim().is_empty() {
+            lines.remove(0);
+        }
+    }
+    lines
+}
+
+fn indentation(line: &str) -> usize
+{
+    line.len() - line.trim_start_matches([' ', '\t']).len()
+}
+
+/// Resolves the escapes in `text`, which starts at `offset` in the source.
+fn unescape(text: &str,
+            offset: TextSize,
+            value: &mut String,
+            errors: &mut Vec<EscapeError>)
+{
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+
+        let res = match chars.next() {
+            None => Err(EscapeErrorKind::LoneSlash),
+            Some((_, 'n')) => Ok('\n'),
+            Some((_, 't')) => Ok('\t'),
+            Some((_, 'r')) => Ok('\r'),
+            Some((_, '0')) => Ok('\0'),
+            Some((_, '\\')) => Ok('\\'),
+            Some((_, '\'')) => Ok('\''),
+            Some((_, '"')) => Ok('"'),
+            Some((_, '$')) => Ok('$'),
+            Some((_, 'u')) => unicode_escape(&mut chars),
+            Some(_) => Err(EscapeErrorKind::UnknownEscape),
+        };
+
+        match res {
+            Ok(c) => value.push(c),
+            Err(kind) => {
+                let end = chars.peek().map_or(text.len(), |&(i, _)| i);
+                let range = TextRange::new(TextSize::from(start as u32),
+                                           TextSize::from(end as u32));
+                errors.push(EscapeError { kind,
+                                          range: range + offset });
+            }
+        }
+    }
+}
+
+/// Decodes the `{..}` part of a `\u{..}` escape.
+fn unicode_escape(chars: &mut std::iter::Peekable<std::str::CharIndices>)
+                  -> Result<char, EscapeErrorKind>
+{
+    if chars.next_if(|&(_, c)| c == '{').is_none() {
+        return Err(EscapeErrorKind::NoBraceInUnicodeEscape);
+    }
+
+    let mut digits = 0;
+    let mut value: u32 = 0;
+    let mut invalid = false;
+    loop {
+        match chars.next() {
+            None => return Err(EscapeErrorKind::UnclosedUnicodeEscape),
+            Some((_, '}')) => break,
+            Some((_, c)) => match c.to_digit(16) {
+                Some(d) if digits < 6 => {
+                    digits += 1;
+                    value = value * 16 + d;
+                }
+                _ => invalid = true,
+            },
+        }
+    }
+
+    if invalid || digits == 0 {
+        return Err(EscapeErrorKind::InvalidUnicodeEscape);
+    }
+    char::from_u32(value).ok_or(EscapeErrorKind::OutOfRangeUnicodeEscape)
+}
@@ -0,0 +1,22 @@
+//! Identifiers, which are the same name when their NFC forms are equal.
+
+use std::borrow::Cow;
+
+use unicode_normalization::{
+    IsNormalized,
+    UnicodeNormalization,
+    is_nfc_quick,
+};
+
+/// The NFC form of the identifier `text`, so that a name spelled with
+/// composed characters and one spelled with decomposed ones are the same.
+/// Most identifiers are NFC already and come back borrowed.
+pub fn normalize(text: &str) -> Cow<'_, str>
+{
+    match is_nfc_quick(text.chars()) {
+        IsNormalized::Yes => Cow::Borrowed(text),
+        IsNormalized::No | IsNormalized::Maybe => {
+            Cow::Owned(text.nfc().collect())
+        }
+    }
+}
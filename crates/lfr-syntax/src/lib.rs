@@ -1,6 +1,7 @@
 pub extern crate rowan;
 
 pub mod ast;
+pub mod ident;
 pub mod span;
 pub mod syntax_kind;
 
@@ -43,3 +44,4 @@ pub type SyntaxNode = rowan::SyntaxNode<LfrLanguage>;
 pub type SyntaxToken = rowan::SyntaxToken<LfrLanguage>;
 pub type NodeOrToken = rowan::NodeOrToken<SyntaxNode, SyntaxToken>;
 pub type SyntaxNodeChildren = rowan::SyntaxNodeChildren<LfrLanguage>;
+pub type SyntaxElementChildren = rowan::SyntaxElementChildren<LfrLanguage>;
@@ -24,6 +24,8 @@ pub enum SyntaxKind
     DOT,
     COLON,
     COLON2,
+    DOT2,
+    DOT2_EQ,
     QMARK,
     EQ,
     PLUS,
@@ -37,6 +39,12 @@ pub enum SyntaxKind
     PIPE2,
     CARET,
     BANG,
+    TILDE,
+    AT,
+    UNDERSCORE,
+    THIN_ARROW,
+    FAT_ARROW,
+    PIPE_R_ANGLE,
     PLUS_EQ,
     MINUS_EQ,
     ASTERISK_EQ,
@@ -52,10 +60,8 @@ pub enum SyntaxKind
     L_ANGLE_EQ,
     R_ANGLE_EQ,
     FN_KW,
-    THIS_KW,
     WHILE_KW,
     FOR_KW,
-    IN_KW,
     CONTINUE_KW,
     BREAK_KW,
     RETURN_KW,
@@ -65,35 +71,67 @@ pub enum SyntaxKind
     TRUE_KW,
     FALSE_KW,
     IMPORT_KW,
+    STRUCT_KW,
+    ENUM_KW,
+    MATCH_KW,
+    THIS_KW,
+    IN_KW,
     AS_KW,
     INT_NUMBER,
     FLOAT_NUMBER,
     CHAR,
     STR,
     MULTILINE_STR,
+    RAW_STR,
     ERROR,
     IDENT,
     WHITESPACE,
     LIFETIME,
     COMMENT,
     BLOCK_COMMENT,
+    DOC_COMMENT,
+    BLOCK_DOC_COMMENT,
     SHEBANG,
     NEWLINE,
+    STR_START,
+    STR_MIDDLE,
+    STR_END,
     ROOT,
+    PRAGMA,
     IMPORT_STMT,
     IMPORT_TARGET,
     NAME,
     STRING_LIT,
+    INTERPOLATED_STR,
     NUMBER_LIT,
+    CHAR_LIT,
     BOOLEAN_LIT,
     LIT_VAL,
     FN_DEF,
+    PARAM_LIST,
+    PARAM,
+    LAMBDA_EXPR,
+    RET_TYPE,
+    STRUCT_DEF,
+    FIELD_LIST,
+    FIELD_DEF,
+    ENUM_DEF,
+    VARIANT_LIST,
+    VARIANT,
+    TYPE_REF,
+    GENERIC_ARG_LIST,
     BLOCK,
     STMT,
     EXPR_STMT,
+    ATTR,
     BIN_EXPR,
+    RANGE_EXPR,
+    PIPE_EXPR,
+    TERNARY_EXPR,
     TUPLE_EXPR,
     ARR_EXPR,
+    MAP_EXPR,
+    MAP_ENTRY,
     PRIMARY_EXPR,
     PATH,
     PATH_FRAG,
@@ -108,13 +146,20 @@ pub enum SyntaxKind
     ELSE_IF_BRANCH,
     ELSE_BRANCH,
     IF_EXPR,
+    MATCH_EXPR,
+    MATCH_ARM_LIST,
+    MATCH_ARM,
     WHILE_STMT,
     FOR_STMT,
-    FOR_IN_EXPR,
     BREAK_STMT,
     CONTINUE_STMT,
     RETURN_STMT,
     DECLARATION_STMT,
+    IDENT_PAT,
+    TUPLE_PAT,
+    WILDCARD_PAT,
+    LIT_PAT,
+    PATH_PAT,
     #[doc(hidden)]
     __LAST,
 }
@@ -123,44 +168,102 @@ impl SyntaxKind
 {
     pub fn is_keyword(self) -> bool
     {
-        match self {
-            FN_KW | THIS_KW | WHILE_KW | FOR_KW | IN_KW | CONTINUE_KW
-            | BREAK_KW | RETURN_KW | IF_KW | ELSE_KW | LET_KW | TRUE_KW
-            | FALSE_KW | IMPORT_KW | AS_KW => true,
-            _ => false,
-        }
+        matches!(self,
+                 FN_KW
+                 | WHILE_KW
+                 | FOR_KW
+                 | CONTINUE_KW
+                 | BREAK_KW
+                 | RETURN_KW
+                 | IF_KW
+                 | ELSE_KW
+                 | LET_KW
+                 | TRUE_KW
+                 | FALSE_KW
+                 | IMPORT_KW
+                 | STRUCT_KW
+                 | ENUM_KW
+                 | MATCH_KW
+                 | THIS_KW
+                 | IN_KW
+                 | AS_KW)
+    }
+
+    pub fn is_contextual_keyword(self) -> bool
+    {
+        matches!(self, THIS_KW | IN_KW | AS_KW)
     }
 
     pub fn is_punct(self) -> bool
     {
-        match self {
-            SEMICOLON | COMMA | L_PAREN | R_PAREN | L_CURLY | R_CURLY
-            | L_BRACK | R_BRACK | L_ANGLE | R_ANGLE | DOT | COLON | COLON2
-            | QMARK | EQ | PLUS | MINUS | ASTERISK | SLASH | PERCENT | AMP
-            | AMP2 | PIPE | PIPE2 | CARET | BANG | PLUS_EQ | MINUS_EQ
-            | ASTERISK_EQ | SLASH_EQ | MODULUS_EQ | AMP_EQ | PIPE_EQ
-            | AMP2_EQ | PIPE2_EQ | CARET_EQ | EQ_EQ | BANG_EQ | L_ANGLE_EQ
-            | R_ANGLE_EQ => true,
-            _ => false,
-        }
+        matches!(self,
+                 SEMICOLON
+                 | COMMA
+                 | L_PAREN
+                 | R_PAREN
+                 | L_CURLY
+                 | R_CURLY
+                 | L_BRACK
+                 | R_BRACK
+                 | L_ANGLE
+                 | R_ANGLE
+                 | DOT
+                 | COLON
+                 | COLON2
+                 | DOT2
+                 | DOT2_EQ
+                 | QMARK
+                 | EQ
+                 | PLUS
+                 | MINUS
+                 | ASTERISK
+                 | SLASH
+                 | PERCENT
+                 | AMP
+                 | AMP2
+                 | PIPE
+                 | PIPE2
+                 | CARET
+                 | BANG
+                 | TILDE
+                 | AT
+                 | UNDERSCORE
+                 | THIN_ARROW
+                 | FAT_ARROW
+                 | PIPE_R_ANGLE
+                 | PLUS_EQ
+                 | MINUS_EQ
+                 | ASTERISK_EQ
+                 | SLASH_EQ
+                 | MODULUS_EQ
+                 | AMP_EQ
+                 | PIPE_EQ
+                 | AMP2_EQ
+                 | PIPE2_EQ
+                 | CARET_EQ
+                 | EQ_EQ
+                 | BANG_EQ
+                 | L_ANGLE_EQ
+                 | R_ANGLE_EQ)
     }
 
     pub fn is_literal(self) -> bool
     {
-        match self {
-            INT_NUMBER | FLOAT_NUMBER | CHAR | STR | MULTILINE_STR => true,
-            _ => false,
-        }
+        matches!(self,
+                 INT_NUMBER
+                 | FLOAT_NUMBER
+                 | CHAR
+                 | STR
+                 | MULTILINE_STR
+                 | RAW_STR)
     }
 
     pub fn from_keyword(ident: &str) -> Option<SyntaxKind>
     {
         let kw = match ident {
             "fn" => FN_KW,
-            "this" => THIS_KW,
             "while" => WHILE_KW,
             "for" => FOR_KW,
-            "in" => IN_KW,
             "continue" => CONTINUE_KW,
             "break" => BREAK_KW,
             "return" => RETURN_KW,
@@ -170,6 +273,22 @@ impl SyntaxKind
             "true" => TRUE_KW,
             "false" => FALSE_KW,
             "import" => IMPORT_KW,
+            "struct" => STRUCT_KW,
+            "enum" => ENUM_KW,
+            "match" => MATCH_KW,
+            _ => return None,
+        };
+        Some(kw)
+    }
+
+    /// The contextual keyword spelled `ident`. The lexer leaves these
+    /// as `IDENT`s, the parser takes them for keywords where the
+    /// grammar expects one.
+    pub fn from_contextual_keyword(ident: &str) -> Option<SyntaxKind>
+    {
+        let kw = match ident {
+            "this" => THIS_KW,
+            "in" => IN_KW,
             "as" => AS_KW,
             _ => return None,
         };
@@ -202,11 +321,14 @@ impl SyntaxKind
             '|' => PIPE,
             '^' => CARET,
             '!' => BANG,
+            '~' => TILDE,
+            '@' => AT,
+            '_' => UNDERSCORE,
             _ => return None,
         };
         Some(tok)
     }
 }
 #[macro_export]
-macro_rules ! T { [;] => { $ crate :: SyntaxKind :: SEMICOLON } ; [,] => { $ crate :: SyntaxKind :: COMMA } ; ['('] => { $ crate :: SyntaxKind :: L_PAREN } ; [')'] => { $ crate :: SyntaxKind :: R_PAREN } ; ['{'] => { $ crate :: SyntaxKind :: L_CURLY } ; ['}'] => { $ crate :: SyntaxKind :: R_CURLY } ; ['['] => { $ crate :: SyntaxKind :: L_BRACK } ; [']'] => { $ crate :: SyntaxKind :: R_BRACK } ; [<] => { $ crate :: SyntaxKind :: L_ANGLE } ; [>] => { $ crate :: SyntaxKind :: R_ANGLE } ; [.] => { $ crate :: SyntaxKind :: DOT } ; [:] => { $ crate :: SyntaxKind :: COLON } ; [::] => { $ crate :: SyntaxKind :: COLON2 } ; [?] => { $ crate :: SyntaxKind :: QMARK } ; [=] => { $ crate :: SyntaxKind :: EQ } ; [+] => { $ crate :: SyntaxKind :: PLUS } ; [-] => { $ crate :: SyntaxKind :: MINUS } ; [*] => { $ crate :: SyntaxKind :: ASTERISK } ; [/] => { $ crate :: SyntaxKind :: SLASH } ; [%] => { $ crate :: SyntaxKind :: PERCENT } ; [&] => { $ crate :: SyntaxKind :: AMP } ; [&&] => { $ crate :: SyntaxKind :: AMP2 } ; [|] => { $ crate :: SyntaxKind :: PIPE } ; [||] => { $ crate :: SyntaxKind :: PIPE2 } ; [^] => { $ crate :: SyntaxKind :: CARET } ; [!] => { $ crate :: SyntaxKind :: BANG } ; [+=] => { $ crate :: SyntaxKind :: PLUS_EQ } ; [-=] => { $ crate :: SyntaxKind :: MINUS_EQ } ; [*=] => { $ crate :: SyntaxKind :: ASTERISK_EQ } ; [/=] => { $ crate :: SyntaxKind :: SLASH_EQ } ; [%=] => { $ crate :: SyntaxKind :: MODULUS_EQ } ; [&=] => { $ crate :: SyntaxKind :: AMP_EQ } ; [|=] => { $ crate :: SyntaxKind :: PIPE_EQ } ; [&&=] => { $ crate :: SyntaxKind :: AMP2_EQ } ; [||=] => { $ crate :: SyntaxKind :: PIPE2_EQ } ; [^=] => { $ crate :: SyntaxKind :: CARET_EQ } ; [==] => { $ crate :: SyntaxKind :: EQ_EQ } ; [!=] => { $ crate :: SyntaxKind :: BANG_EQ } ; [<=] => { $ crate :: SyntaxKind :: L_ANGLE_EQ } ; [>=] => { $ crate :: SyntaxKind :: R_ANGLE_EQ } ; [fn] => { $ crate :: SyntaxKind :: FN_KW } ; [this] => { $ crate :: SyntaxKind :: THIS_KW } ; [while] => { $ crate :: SyntaxKind :: WHILE_KW } ; [for] => { $ crate :: SyntaxKind :: FOR_KW } ; [in] => { $ crate :: SyntaxKind :: IN_KW } ; [continue] => { $ crate :: SyntaxKind :: CONTINUE_KW } ; [break] => { $ crate :: SyntaxKind :: BREAK_KW } ; [return] => { $ crate :: SyntaxKind :: RETURN_KW } ; [if] => { $ crate :: SyntaxKind :: IF_KW } ; [else] => { $ crate :: SyntaxKind :: ELSE_KW } ; [let] => { $ crate :: SyntaxKind :: LET_KW } ; [true] => { $ crate :: SyntaxKind :: TRUE_KW } ; [false] => { $ crate :: SyntaxKind :: FALSE_KW } ; [import] => { $ crate :: SyntaxKind :: IMPORT_KW } ; [as] => { $ crate :: SyntaxKind :: AS_KW } ; [comment] => { $ crate :: SyntaxKind :: COMMENT } ; [block_comment] => { $ crate :: SyntaxKind :: COMMENT } ; [ident] => { $ crate :: SyntaxKind :: IDENT } ; [int_number] => { $ crate :: SyntaxKind :: INT_NUMBER } ; [str] => { $ crate :: SyntaxKind :: STR } ; [multiline_str] => { $ crate :: SyntaxKind :: MULTILINE_STR } ; [whitespace] => { $ crate :: SyntaxKind :: WHITESPACE } ; [newline] => { $ crate :: SyntaxKind :: NEWLINE } ; }
+macro_rules ! T { [;] => { $ crate :: SyntaxKind :: SEMICOLON } ; [,] => { $ crate :: SyntaxKind :: COMMA } ; ['('] => { $ crate :: SyntaxKind :: L_PAREN } ; [')'] => { $ crate :: SyntaxKind :: R_PAREN } ; ['{'] => { $ crate :: SyntaxKind :: L_CURLY } ; ['}'] => { $ crate :: SyntaxKind :: R_CURLY } ; ['['] => { $ crate :: SyntaxKind :: L_BRACK } ; [']'] => { $ crate :: SyntaxKind :: R_BRACK } ; [<] => { $ crate :: SyntaxKind :: L_ANGLE } ; [>] => { $ crate :: SyntaxKind :: R_ANGLE } ; [.] => { $ crate :: SyntaxKind :: DOT } ; [:] => { $ crate :: SyntaxKind :: COLON } ; [::] => { $ crate :: SyntaxKind :: COLON2 } ; [..] => { $ crate :: SyntaxKind :: DOT2 } ; [..=] => { $ crate :: SyntaxKind :: DOT2_EQ } ; [?] => { $ crate :: SyntaxKind :: QMARK } ; [=] => { $ crate :: SyntaxKind :: EQ } ; [+] => { $ crate :: SyntaxKind :: PLUS } ; [-] => { $ crate :: SyntaxKind :: MINUS } ; [*] => { $ crate :: SyntaxKind :: ASTERISK } ; [/] => { $ crate :: SyntaxKind :: SLASH } ; [%] => { $ crate :: SyntaxKind :: PERCENT } ; [&] => { $ crate :: SyntaxKind :: AMP } ; [&&] => { $ crate :: SyntaxKind :: AMP2 } ; [|] => { $ crate :: SyntaxKind :: PIPE } ; [||] => { $ crate :: SyntaxKind :: PIPE2 } ; [^] => { $ crate :: SyntaxKind :: CARET } ; [!] => { $ crate :: SyntaxKind :: BANG } ; [~] => { $ crate :: SyntaxKind :: TILDE } ; [@] => { $ crate :: SyntaxKind :: AT } ; [_] => { $ crate :: SyntaxKind :: UNDERSCORE } ; [->] => { $ crate :: SyntaxKind :: THIN_ARROW } ; [=>] => { $ crate :: SyntaxKind :: FAT_ARROW } ; [|>] => { $ crate :: SyntaxKind :: PIPE_R_ANGLE } ; [+=] => { $ crate :: SyntaxKind :: PLUS_EQ } ; [-=] => { $ crate :: SyntaxKind :: MINUS_EQ } ; [*=] => { $ crate :: SyntaxKind :: ASTERISK_EQ } ; [/=] => { $ crate :: SyntaxKind :: SLASH_EQ } ; [%=] => { $ crate :: SyntaxKind :: MODULUS_EQ } ; [&=] => { $ crate :: SyntaxKind :: AMP_EQ } ; [|=] => { $ crate :: SyntaxKind :: PIPE_EQ } ; [&&=] => { $ crate :: SyntaxKind :: AMP2_EQ } ; [||=] => { $ crate :: SyntaxKind :: PIPE2_EQ } ; [^=] => { $ crate :: SyntaxKind :: CARET_EQ } ; [==] => { $ crate :: SyntaxKind :: EQ_EQ } ; [!=] => { $ crate :: SyntaxKind :: BANG_EQ } ; [<=] => { $ crate :: SyntaxKind :: L_ANGLE_EQ } ; [>=] => { $ crate :: SyntaxKind :: R_ANGLE_EQ } ; [fn] => { $ crate :: SyntaxKind :: FN_KW } ; [while] => { $ crate :: SyntaxKind :: WHILE_KW } ; [for] => { $ crate :: SyntaxKind :: FOR_KW } ; [continue] => { $ crate :: SyntaxKind :: CONTINUE_KW } ; [break] => { $ crate :: SyntaxKind :: BREAK_KW } ; [return] => { $ crate :: SyntaxKind :: RETURN_KW } ; [if] => { $ crate :: SyntaxKind :: IF_KW } ; [else] => { $ crate :: SyntaxKind :: ELSE_KW } ; [let] => { $ crate :: SyntaxKind :: LET_KW } ; [true] => { $ crate :: SyntaxKind :: TRUE_KW } ; [false] => { $ crate :: SyntaxKind :: FALSE_KW } ; [import] => { $ crate :: SyntaxKind :: IMPORT_KW } ; [struct] => { $ crate :: SyntaxKind :: STRUCT_KW } ; [enum] => { $ crate :: SyntaxKind :: ENUM_KW } ; [match] => { $ crate :: SyntaxKind :: MATCH_KW } ; [this] => { $ crate :: SyntaxKind :: THIS_KW } ; [in] => { $ crate :: SyntaxKind :: IN_KW } ; [as] => { $ crate :: SyntaxKind :: AS_KW } ; [int_number] => { $ crate :: SyntaxKind :: INT_NUMBER } ; [float_number] => { $ crate :: SyntaxKind :: FLOAT_NUMBER } ; [char] => { $ crate :: SyntaxKind :: CHAR } ; [str] => { $ crate :: SyntaxKind :: STR } ; [multiline_str] => { $ crate :: SyntaxKind :: MULTILINE_STR } ; [raw_str] => { $ crate :: SyntaxKind :: RAW_STR } ; [error] => { $ crate :: SyntaxKind :: ERROR } ; [ident] => { $ crate :: SyntaxKind :: IDENT } ; [whitespace] => { $ crate :: SyntaxKind :: WHITESPACE } ; [lifetime] => { $ crate :: SyntaxKind :: LIFETIME } ; [comment] => { $ crate :: SyntaxKind :: COMMENT } ; [block_comment] => { $ crate :: SyntaxKind :: BLOCK_COMMENT } ; [doc_comment] => { $ crate :: SyntaxKind :: DOC_COMMENT } ; [block_doc_comment] => { $ crate :: SyntaxKind :: BLOCK_DOC_COMMENT } ; [shebang] => { $ crate :: SyntaxKind :: SHEBANG } ; [newline] => { $ crate :: SyntaxKind :: NEWLINE } ; [str_start] => { $ crate :: SyntaxKind :: STR_START } ; [str_middle] => { $ crate :: SyntaxKind :: STR_MIDDLE } ; [str_end] => { $ crate :: SyntaxKind :: STR_END } ; }
 pub use T;
@@ -38,8 +38,9 @@ fn read(db: &dyn VfsDatabase, path: PathBuf) -> String
     std::fs::read_to_string(&path).unwrap_or_default()
 }
 
+// nothing reads the events yet, the receiver only keeps the channel open.
 pub struct VfsWatcher(Arc<Mutex<INotifyWatcher>>,
-                      Receiver<DebouncedEvent>);
+                      #[allow(dead_code)] Receiver<DebouncedEvent>);
 
 impl VfsWatcher
 {
@@ -60,6 +61,8 @@ impl VfsWatcher
 pub trait FileWatcher
 {
     fn watch(&self, path: &Path);
+    // `ReadQuery` is keyed by a `PathBuf`.
+    #[allow(clippy::ptr_arg)]
     fn did_change_file(&mut self, path: &PathBuf);
 }
 
@@ -8,6 +8,16 @@ pub(crate) struct KindsSrc<'a>
     pub(crate) literals:            &'a [&'a str],
     pub(crate) tokens:              &'a [&'a str],
     pub(crate) nodes:               &'a [&'a str],
+    /// The regexes the lexer matches literals and tokens with.
+    /// `ERROR` is attached to the `#[error]` variant.
+    pub(crate) regexes:             &'a [(&'a str, &'a str)],
+    /// Callbacks (in `lfr_parser::lexer`) that finish lexing the tokens
+    /// whose regexes only match their start.
+    /// `STR_START`, `STR_MIDDLE` and `STR_END` have no regex: the lexer
+    /// produces them while it is inside an interpolated string.
+    /// `DOC_COMMENT` and `BLOCK_DOC_COMMENT` have none either: they are
+    /// comments the lexer tells apart by their text.
+    pub(crate) callbacks:           &'a [(&'a str, &'a str)],
 }
 
 pub(crate) const KINDS_SRC: KindsSrc =
@@ -25,6 +35,8 @@ pub(crate) const KINDS_SRC: KindsSrc =
                                       (".", "DOT"),
                                       (":", "COLON"),
                                       ("::", "COLON2"),
+                                      ("..", "DOT2"),
+                                      ("..=", "DOT2_EQ"),
                                       ("?", "QMARK"),
                                       ("=", "EQ"),
                                       ("+", "PLUS"),
@@ -38,6 +50,12 @@ pub(crate) const KINDS_SRC: KindsSrc =
                                       ("||", "PIPE2"),
                                       ("^", "CARET"),
                                       ("!", "BANG"),
+                                      ("~", "TILDE"),
+                                      ("@", "AT"),
+                                      ("_", "UNDERSCORE"),
+                                      ("->", "THIN_ARROW"),
+                                      ("=>", "FAT_ARROW"),
+                                      ("|>", "PIPE_R_ANGLE"),
                                       ("+=", "PLUS_EQ"),
                                       ("-=", "MINUS_EQ"),
                                       ("*=", "ASTERISK_EQ"),
@@ -52,39 +70,66 @@ pub(crate) const KINDS_SRC: KindsSrc =
                                       ("!=", "BANG_EQ"),
                                       ("<=", "L_ANGLE_EQ"),
                                       (">=", "R_ANGLE_EQ")],
-               keywords:            &["fn", "this", "while", "for", "in",
-                                      "continue", "break", "return", "if",
-                                      "else", "let", "true", "false",
-                                      "import", "as"],
-               contextual_keywords: &[],
+               keywords:            &["fn", "while", "for", "continue",
+                                      "break", "return", "if", "else", "let",
+                                      "true", "false", "import", "struct",
+                                      "enum", "match"],
+               contextual_keywords: &["this", "in", "as"],
                literals:            &["INT_NUMBER",
                                       "FLOAT_NUMBER",
                                       "CHAR",
                                       "STR",
-                                      "MULTILINE_STR"],
+                                      "MULTILINE_STR",
+                                      "RAW_STR"],
                tokens:              &["ERROR",
                                       "IDENT",
                                       "WHITESPACE",
                                       "LIFETIME",
                                       "COMMENT",
                                       "BLOCK_COMMENT",
+                                      "DOC_COMMENT",
+                                      "BLOCK_DOC_COMMENT",
                                       "SHEBANG",
-                                      "NEWLINE"],
+                                      "NEWLINE",
+                                      "STR_START",
+                                      "STR_MIDDLE",
+                                      "STR_END"],
                nodes:               &["ROOT",
+                                      "PRAGMA",
                                       "IMPORT_STMT",
                                       "IMPORT_TARGET",
                                       "NAME",
                                       "STRING_LIT",
+                                      "INTERPOLATED_STR",
                                       "NUMBER_LIT",
+                                      "CHAR_LIT",
                                       "BOOLEAN_LIT",
                                       "LIT_VAL",
                                       "FN_DEF",
+                                      "PARAM_LIST",
+                                      "PARAM",
+                                      "LAMBDA_EXPR",
+                                      "RET_TYPE",
+                                      "STRUCT_DEF",
+                                      "FIELD_LIST",
+                                      "FIELD_DEF",
+                                      "ENUM_DEF",
+                                      "VARIANT_LIST",
+                                      "VARIANT",
+                                      "TYPE_REF",
+                                      "GENERIC_ARG_LIST",
                                       "BLOCK",
                                       "STMT",
                                       "EXPR_STMT",
+                                      "ATTR",
                                       "BIN_EXPR",
+                                      "RANGE_EXPR",
+                                      "PIPE_EXPR",
+                                      "TERNARY_EXPR",
                                       "TUPLE_EXPR",
                                       "ARR_EXPR",
+                                      "MAP_EXPR",
+                                      "MAP_ENTRY",
                                       "PRIMARY_EXPR",
                                       "PATH",
                                       "PATH_FRAG",
@@ -99,13 +144,39 @@ pub(crate) const KINDS_SRC: KindsSrc =
                                       "ELSE_IF_BRANCH",
                                       "ELSE_BRANCH",
                                       "IF_EXPR",
+                                      "MATCH_EXPR",
+                                      "MATCH_ARM_LIST",
+                                      "MATCH_ARM",
                                       "WHILE_STMT",
                                       "FOR_STMT",
-                                      "FOR_IN_EXPR",
                                       "BREAK_STMT",
                                       "CONTINUE_STMT",
                                       "RETURN_STMT",
-                                      "DECLARATION_STMT"], };
+                                      "DECLARATION_STMT",
+                                      "IDENT_PAT",
+                                      "TUPLE_PAT",
+                                      "WILDCARD_PAT",
+                                      "LIT_PAT",
+                                      "PATH_PAT"],
+               regexes:             &[("IDENT", r#"[\p{XID_Start}_]\p{XID_Continue}*"#),
+                                      ("INT_NUMBER",
+                                       r#"([1-9][0-9]*|0x[0-9a-fA-F]+|0b[01]+|0[0-7]+|0)[uU]?[lL]?"#),
+                                      ("FLOAT_NUMBER",
+                                       r#"[0-9]+(\.[0-9]+([eE][+-]?[0-9]+)?|[eE][+-]?[0-9]+)"#),
+                                      ("CHAR",
+                                       r#"c'(\\(u\{[^}'\n]*\}|[^\n])|[^'\\\n])'"#),
+                                      ("STR", r#"['"]"#),
+                                      ("MULTILINE_STR",
+                                       r#"'''([^']|'[^']|''[^'])*'''"#),
+                                      ("RAW_STR", r#"r#*['"]"#),
+                                      ("COMMENT", r#"//[^\n]*"#),
+                                      ("BLOCK_COMMENT",
+                                       r#"/\*([^*]|\**[^*/])*\*+/"#),
+                                      ("WHITESPACE", r#"[ \t\r]+"#),
+                                      ("NEWLINE", r#"\n"#),
+                                      ("ERROR", r#"/\*([^*]|\*+[^*/])*\*?"#)],
+               callbacks:           &[("STR", "lex_str"),
+                                      ("RAW_STR", "lex_raw_str")], };
 
 #[derive(Default, Debug)]
 pub(crate) struct AstSrc
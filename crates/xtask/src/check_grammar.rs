@@ -0,0 +1,290 @@
+//! Checks the trees the parser builds against `lfr.ungram`.
+//!
+//! Every inline test and every `ok` test-data file is parsed, and each node
+//! of the resulting tree is matched against the rule for its kind, trivia
+//! aside. The accessors generated from the grammar silently return `None`
+//! on the nodes that don't match.
+
+use std::collections::{
+    BTreeMap,
+    BTreeSet,
+    HashMap,
+};
+use std::path::Path;
+
+use itertools::Itertools;
+use lfr_parser::lfr_syntax::{
+    NodeOrToken,
+    SyntaxKind,
+    SyntaxNode,
+};
+use ungrammar::{
+    Grammar,
+    Node,
+    Rule,
+};
+use walkdir::WalkDir;
+
+use crate::ast_src::KINDS_SRC;
+use crate::gen_syntax::to_upper_snake_case;
+use crate::parser_tests::inline_tests;
+
+/// Checks every parsed tree, printing the mismatches found; returns how many
+/// there are.
+pub fn check_grammar() -> usize
+{
+    let grammar: Grammar = cg::read_file(cg::ws_path!("crates/lfr-syntax/\
+                                                       lfr.ungram")).parse()
+                                                                    .unwrap();
+    let checker = Checker::new(&grammar);
+
+    // mismatches are keyed by what went wrong, so that a wrapper the parser
+    // emits everywhere is reported once.
+    let mut mismatches: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut check = |source: String, text: &str| {
+        let (green, _) = lfr_parser::parser::parse(text);
+        let root = SyntaxNode::new_root(green);
+        for node in root.descendants() {
+            if let Some(mismatch) = checker.check(&node) {
+                mismatches.entry(mismatch)
+                          .or_default()
+                          .insert(source.clone());
+            }
+        }
+    };
+
+    let root = cg::ws_path!("");
+    for (file, test) in inline_tests().into_iter().filter(|(_, it)| it.ok) {
+        let file = file.strip_prefix(&root).unwrap_or(&file).display();
+        check(format!("{}:{}", file, test.name),
+              &test.text);
+    }
+    for file in test_data_files(&cg::ws_path!("test_data/parser")) {
+        let source = file.strip_prefix(&root).unwrap_or(&file).display();
+        check(source.to_string(),
+              &cg::read_file(&file));
+    }
+
+    for (mismatch, sources) in &mismatches {
+        println!("{}", mismatch);
+        for source in sources.iter().take(3) {
+            println!("    in {}", source);
+        }
+        if sources.len() > 3 {
+            println!("    and {} more", sources.len() - 3);
+        }
+    }
+    mismatches.len()
+}
+
+/// The `.lfr` files in the `ok` directories under `dir`.
+fn test_data_files(dir: &Path) -> Vec<std::path::PathBuf>
+{
+    if !dir.exists() {
+        return vec![];
+    }
+    let mut files =
+        WalkDir::new(dir).into_iter()
+                         .map(|it| it.unwrap().into_path())
+                         .filter(|it| {
+                             it.extension().is_some_and(|it| it == "lfr")
+                         })
+                         .filter(|it| {
+                             it.components().any(|it| it.as_os_str() == "ok")
+                         })
+                         .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+/// A child of a node, as the grammar sees it.
+enum Child
+{
+    Node(String),
+    Token(String),
+}
+
+impl std::fmt::Display for Child
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self {
+            Child::Node(kind) | Child::Token(kind) => f.write_str(kind),
+        }
+    }
+}
+
+struct Checker<'a>
+{
+    grammar: &'a Grammar,
+    /// The grammar nodes by the `SyntaxKind` they are built from.
+    rules:   HashMap<String, Node>,
+    /// The `SyntaxKind` of each grammar token, by its name.
+    tokens:  HashMap<String, String>,
+}
+
+impl<'a> Checker<'a>
+{
+    fn new(grammar: &'a Grammar) -> Self
+    {
+        let rules =
+            grammar.iter()
+                   .map(|it| (to_upper_snake_case(&grammar[it].name), it))
+                   .collect();
+
+        let mut tokens = HashMap::new();
+        for &(punct, kind) in KINDS_SRC.punct {
+            tokens.insert(punct.to_string(), kind.to_string());
+        }
+        for &kw in KINDS_SRC.keywords
+                            .iter()
+                            .chain(KINDS_SRC.contextual_keywords)
+        {
+            tokens.insert(kw.to_string(),
+                          format!("{}_KW", kw.to_ascii_uppercase()));
+        }
+
+        Self { grammar,
+               rules,
+               tokens }
+    }
+
+    /// Describes how `node` doesn't match the rule for its kind, if it
+    /// doesn't.
+    fn check(&self, node: &SyntaxNode) -> Option<String>
+    {
+        let kind = format!("{:?}", node.kind());
+        let grammar_node = match self.rules.get(&kind) {
+            Some(&it) => it,
+            None => return Some(format!("{}: no rule in lfr.ungram", kind)),
+        };
+        // the nodes of enums only ever exist as one of their variants.
+        if self.variants(grammar_node).is_some() {
+            return Some(format!("{}: built from the enum `{}`",
+                                kind, self.grammar[grammar_node].name));
+        }
+
+        let children = node.children_with_tokens()
+                           .filter(|it| !is_trivia(it.kind()))
+                           .map(|it| {
+                               let kind = format!("{:?}", it.kind());
+                               match it {
+                                   NodeOrToken::Node(_) => Child::Node(kind),
+                                   NodeOrToken::Token(_) => Child::Token(kind),
+                               }
+                           })
+                           .collect::<Vec<_>>();
+        let rule = &self.grammar[grammar_node].rule;
+        if self.ends(rule, &children, 0).contains(&children.len()) {
+            return None;
+        }
+        Some(format!("{}: [{}] doesn't match `{}`",
+                     kind,
+                     children.iter().map(|it| it.to_string()).join(", "),
+                     self.grammar[grammar_node].name))
+    }
+
+    /// The positions right after each way `rule` can match `children`
+    /// starting at `start`.
+    fn ends(&self,
+            rule: &Rule,
+            children: &[Child],
+            start: usize)
+            -> BTreeSet<usize>
+    {
+        let mut res = BTreeSet::new();
+        match rule {
+            Rule::Labeled { rule, .. } => {
+                return self.ends(rule, children, start)
+            }
+            Rule::Node(node) => {
+                if let Some(Child::Node(kind)) = children.get(start) {
+                    if self.explains(*node, kind) {
+                        res.insert(start + 1);
+                    }
+                }
+            }
+            Rule::Token(token) => {
+                if let Some(Child::Token(kind)) = children.get(start) {
+                    if self.token_kind(&self.grammar[*token].name) == *kind {
+                        res.insert(start + 1);
+                    }
+                }
+            }
+            Rule::Seq(rules) => {
+                res.insert(start);
+                for rule in rules {
+                    res = res.iter()
+                             .flat_map(|&it| self.ends(rule, children, it))
+                             .collect();
+                }
+            }
+            Rule::Alt(rules) => {
+                for rule in rules {
+                    res.extend(self.ends(rule, children, start));
+                }
+            }
+            Rule::Opt(rule) => {
+                res.insert(start);
+                res.extend(self.ends(rule, children, start));
+            }
+            Rule::Rep(rule) => {
+                res.insert(start);
+                let mut frontier = vec![start];
+                while let Some(pos) = frontier.pop() {
+                    for end in self.ends(rule, children, pos) {
+                        if res.insert(end) {
+                            frontier.push(end);
+                        }
+                    }
+                }
+            }
+        }
+        res
+    }
+
+    /// Whether a node of `kind` can stand where the grammar expects `node`.
+    fn explains(&self, node: Node, kind: &str) -> bool
+    {
+        if to_upper_snake_case(&self.grammar[node].name) == kind {
+            return true;
+        }
+        self.variants(node)
+            .is_some_and(|variants| {
+                variants.into_iter().any(|it| self.explains(it, kind))
+            })
+    }
+
+    /// The variants of `node`, if it is an enum.
+    fn variants(&self, node: Node) -> Option<Vec<Node>>
+    {
+        match &self.grammar[node].rule {
+            Rule::Alt(alts) => alts.iter()
+                                   .map(|it| match it {
+                                       Rule::Node(it) => Some(*it),
+                                       _ => None,
+                                   })
+                                   .collect(),
+            _ => None,
+        }
+    }
+
+    fn token_kind(&self, name: &str) -> String
+    {
+        match self.tokens.get(name) {
+            Some(kind) => kind.clone(),
+            None => name.to_ascii_uppercase(),
+        }
+    }
+}
+
+fn is_trivia(kind: SyntaxKind) -> bool
+{
+    matches!(kind,
+             SyntaxKind::WHITESPACE
+             | SyntaxKind::COMMENT
+             | SyntaxKind::BLOCK_COMMENT
+             | SyntaxKind::DOC_COMMENT
+             | SyntaxKind::BLOCK_DOC_COMMENT
+             | SyntaxKind::NEWLINE)
+}
@@ -0,0 +1,50 @@
+//! Extracts the inline parser tests into `test_data/parser/inline`, where
+//! the `lfr-parser` tests pick them up.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::parser_tests::inline_tests;
+
+pub fn gen_parser_tests()
+{
+    let files = inline_test_files();
+    for (path, text) in &files {
+        cg::ensure_file_contents(path, text);
+    }
+
+    // drop the tests that were removed from the sources, along with their
+    // dumps.
+    for dir in ["ok", "err"] {
+        let dir = cg::ws_path!("test_data/parser/inline").join(dir);
+        if !dir.exists() {
+            continue;
+        }
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if !files.contains_key(&path.with_extension("lfr")) {
+                fs::remove_file(path).unwrap();
+            }
+        }
+    }
+}
+
+/// The file each inline test should be written to, with its contents.
+pub(crate) fn inline_test_files() -> HashMap<PathBuf, String>
+{
+    let mut res = HashMap::new();
+    for (file, test) in inline_tests() {
+        let dir = if test.ok { "ok" } else { "err" };
+        let path =
+            cg::ws_path!("test_data/parser/inline").join(dir)
+                                                   .join(format!("{}.lfr",
+                                                                 test.name));
+        if res.insert(path, test.text).is_some() {
+            panic!("duplicate test `{}` in {}",
+                   test.name,
+                   file.display());
+        }
+    }
+    res
+}
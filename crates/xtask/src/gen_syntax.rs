@@ -36,6 +36,11 @@ pub fn gen_syntax()
     cg::ensure_file_contents(&syntax_kinds_file, &syntax_kinds);
     cg::reformat(&syntax_kinds_file);
 
+    let lexer = generate_lexer(KINDS_SRC);
+    let lexer_file = cg::ws_path!("crates/lfr-parser/src/lexer/generated.rs");
+    cg::ensure_file_contents(&lexer_file, &lexer);
+    cg::reformat(&lexer_file);
+
     let grammar =
         cg::read_file(cg::ws_path!("crates/lfr-syntax/lfr.ungram")).parse()
                                                                    .unwrap();
@@ -182,10 +187,7 @@ fn generate_nodes(kinds: KindsSrc<'_>, grammar: &AstSrc) -> String
             let ast_node = quote! {
                 impl AstNode for #name {
                     fn can_cast(kind: SyntaxKind) -> bool {
-                        match kind {
-                            #(#kinds)|* => true,
-                            _ => false,
-                        }
+                        matches!(kind, #(#kinds)|*)
                     }
                     fn cast(syntax: SyntaxNode) -> Option<Self> {
                         let res = match syntax.kind() {
@@ -265,10 +267,7 @@ fn generate_nodes(kinds: KindsSrc<'_>, grammar: &AstSrc) -> String
                     }
                     impl AstNode for #name {
                         fn can_cast(kind: SyntaxKind) -> bool {
-                            match kind {
-                                #(#kinds)|* => true,
-                                _ => false,
-                            }
+                            matches!(kind, #(#kinds)|*)
                         }
                         fn cast(syntax: SyntaxNode) -> Option<Self> {
                             Self::can_cast(syntax.kind()).then(|| #name { syntax })
@@ -367,6 +366,9 @@ fn generate_syntax_kinds(grammar: KindsSrc<'_>) -> String
         if "{}[]()".contains(token) {
             let c = token.chars().next().unwrap();
             quote! { #c }
+        } else if *token == "_" {
+            // `_` is an identifier to proc-macro2, not a punct.
+            quote! { _ }
         } else {
             let cs = token.chars().map(|c| Punct::new(c, Spacing::Joint));
             quote! { #(#cs)* }
@@ -384,6 +386,15 @@ fn generate_syntax_kinds(grammar: KindsSrc<'_>) -> String
                                 format_ident!("{}_KW", to_upper_snake_case(kw))
                             });
 
+    let contextual_keywords_values = &grammar.contextual_keywords;
+    let contextual_keywords =
+        contextual_keywords_values.iter()
+                                  .map(|kw| {
+                                      format_ident!("{}_KW",
+                                                    to_upper_snake_case(kw))
+                                  })
+                                  .collect::<Vec<_>>();
+
     let all_keywords_values = grammar.keywords
                                      .iter()
                                      .chain(grammar.contextual_keywords.iter())
@@ -412,6 +423,14 @@ fn generate_syntax_kinds(grammar: KindsSrc<'_>) -> String
                        .map(|name| format_ident!("{}", name))
                        .collect::<Vec<_>>();
 
+    let named_tokens = literals.iter().chain(tokens.iter()).collect::<Vec<_>>();
+    let named_tokens_values =
+        named_tokens.iter().map(|name| {
+                               format_ident!("{}",
+                                             name.to_string()
+                                                 .to_ascii_lowercase())
+                           });
+
     let ast = quote! {
         #![allow(bad_style, missing_docs, unreachable_pub)]
         /// The kind of syntax node, e.g. `IDENT`, `USE_KW`, or `STRUCT`.
@@ -438,24 +457,19 @@ fn generate_syntax_kinds(grammar: KindsSrc<'_>) -> String
 
         impl SyntaxKind {
             pub fn is_keyword(self) -> bool {
-                match self {
-                    #(#all_keywords)|* => true,
-                    _ => false,
-                }
+                matches!(self, #(#all_keywords)|*)
+            }
+
+            pub fn is_contextual_keyword(self) -> bool {
+                matches!(self, #(#contextual_keywords)|*)
             }
 
             pub fn is_punct(self) -> bool {
-                match self {
-                    #(#punctuation)|* => true,
-                    _ => false,
-                }
+                matches!(self, #(#punctuation)|*)
             }
 
             pub fn is_literal(self) -> bool {
-                match self {
-                    #(#literals)|* => true,
-                    _ => false,
-                }
+                matches!(self, #(#literals)|*)
             }
 
             pub fn from_keyword(ident: &str) -> Option<SyntaxKind> {
@@ -466,6 +480,17 @@ fn generate_syntax_kinds(grammar: KindsSrc<'_>) -> String
                 Some(kw)
             }
 
+            /// The contextual keyword spelled `ident`. The lexer leaves these
+            /// as `IDENT`s, the parser takes them for keywords where the
+            /// grammar expects one.
+            pub fn from_contextual_keyword(ident: &str) -> Option<SyntaxKind> {
+                let kw = match ident {
+                    #(#contextual_keywords_values => #contextual_keywords,)*
+                    _ => return None,
+                };
+                Some(kw)
+            }
+
             pub fn from_char(c: char) -> Option<SyntaxKind> {
                 let tok = match c {
                     #(#single_byte_tokens_values => #single_byte_tokens,)*
@@ -479,14 +504,7 @@ fn generate_syntax_kinds(grammar: KindsSrc<'_>) -> String
         macro_rules! T {
             #([#punctuation_values] => { $crate::SyntaxKind::#punctuation };)*
             #([#all_keywords_idents] => { $crate::SyntaxKind::#all_keywords };)*
-            [comment] => { $crate::SyntaxKind::COMMENT };
-            [block_comment] => { $crate::SyntaxKind::COMMENT };
-            [ident] => { $crate::SyntaxKind::IDENT };
-            [int_number] => { $crate::SyntaxKind::INT_NUMBER };
-            [str] => { $crate::SyntaxKind::STR };
-            [multiline_str] => { $crate::SyntaxKind::MULTILINE_STR };
-            [whitespace] => { $crate::SyntaxKind::WHITESPACE };
-            [newline] => { $crate::SyntaxKind::NEWLINE };
+            #([#named_tokens_values] => { $crate::SyntaxKind::#named_tokens };)*
         }
         pub use T;
     };
@@ -494,7 +512,92 @@ fn generate_syntax_kinds(grammar: KindsSrc<'_>) -> String
     cg::add_preamble(&ast.to_string(), "gen_syntax")
 }
 
-fn to_upper_snake_case(s: &str) -> String
+fn generate_lexer(grammar: KindsSrc<'_>) -> String
+{
+    // composite punctuation is glued together by the parser, so the lexer
+    // only needs to know about the single-char tokens.
+    let (punct_values, punct): (Vec<_>, Vec<_>) =
+        grammar.punct
+               .iter()
+               .filter(|(token, _name)| token.len() == 1)
+               .map(|(token, name)| (*token, *name))
+               .unzip();
+
+    let keywords_values = grammar.keywords;
+    let keywords =
+        keywords_values.iter()
+                       .map(|kw| format!("{}_KW", to_upper_snake_case(kw)))
+                       .collect::<Vec<_>>();
+
+    let (regex_values, regex_tokens): (Vec<_>, Vec<_>) =
+        grammar.regexes
+               .iter()
+               .filter(|(name, _regex)| *name != "ERROR")
+               .map(|(name, regex)| (*regex, *name))
+               .unzip();
+    let error_regex = grammar.regexes
+                             .iter()
+                             .find(|(name, _regex)| *name == "ERROR")
+                             .map(|(_name, regex)| *regex)
+                             .into_iter();
+
+    let variant = |kind: &str| format_ident!("{}", to_pascal_case(kind));
+    let punct_variants = punct.iter().map(|it| variant(it));
+    let keywords_variants = keywords.iter().map(|it| variant(it));
+    let regex_variants = regex_tokens.iter().map(|it| variant(it));
+    let regex_attrs =
+        regex_values.iter()
+                    .zip(regex_tokens.iter())
+                    .map(|(regex, name)| {
+                        match grammar.callbacks
+                                     .iter()
+                                     .find(|(it, _callback)| it == name)
+                        {
+                            Some((_name, callback)) => {
+                                let callback = format_ident!("{}", callback);
+                                quote! { #[regex(#regex, super::#callback)] }
+                            }
+                            None => quote! { #[regex(#regex)] },
+                        }
+                    });
+
+    let kinds = punct.iter()
+                     .copied()
+                     .chain(keywords.iter().map(|it| it.as_str()))
+                     .chain(regex_tokens.iter().copied())
+                     .collect::<Vec<_>>();
+    let kinds_variants = kinds.iter().map(|it| variant(it));
+    let kinds_kinds = kinds.iter().map(|it| format_ident!("{}", it));
+
+    let ast = quote! {
+        use lfr_syntax::SyntaxKind::{self, *};
+        use logos::Logos;
+
+        /// The tokens `logos` splits the source into.
+        #[derive(Logos, Copy, Clone, Debug, PartialEq, PartialOrd, Eq)]
+        pub enum Tk {
+            #(#[token(#punct_values)] #punct_variants,)*
+            #(#[token(#keywords_values)] #keywords_variants,)*
+            #(#regex_attrs #regex_variants,)*
+            #[error]
+            #(#[regex(#error_regex)])*
+            Error,
+        }
+
+        impl From<Tk> for SyntaxKind {
+            fn from(tk: Tk) -> Self {
+                match tk {
+                    #(Tk::#kinds_variants => #kinds_kinds,)*
+                    Tk::Error => ERROR,
+                }
+            }
+        }
+    };
+
+    cg::add_preamble(&ast.to_string(), "gen_syntax")
+}
+
+pub(crate) fn to_upper_snake_case(s: &str) -> String
 {
     let mut buf = String::with_capacity(s.len());
     let mut prev = false;
@@ -583,6 +686,8 @@ impl Field
                     "." => "dot",
                     ":" => "colon",
                     "::" => "colon2",
+                    ".." => "dot2",
+                    "..=" => "dot2_eq",
                     "?" => "qmark",
                     "=" => "eq",
                     "+" => "plus",
@@ -596,6 +701,12 @@ impl Field
                     "||" => "pipe2",
                     "^" => "caret",
                     "!" => "bang",
+                    "~" => "tilde",
+                    "@" => "at",
+                    "_" => "underscore",
+                    "->" => "thin_arrow",
+                    "=>" => "fat_arrow",
+                    "|>" => "pipe_r_angle",
                     "+=" => "plus_eq",
                     "-=" => "minus_eq",
                     "*=" => "asterisk_eq",
@@ -636,12 +747,13 @@ impl Field
 
 fn lower(grammar: &Grammar) -> AstSrc
 {
-    let mut res = AstSrc { tokens: "Whitespace Comment BlockComment Str \
-                                    MultilineStr IntNumber FloatNumber \
-                                    Ident"
-                                          .split_ascii_whitespace()
-                                          .map(|it| it.to_string())
-                                          .collect::<Vec<_>>(),
+    let mut res = AstSrc { tokens:
+                               "Whitespace Comment BlockComment DocComment \
+                                BlockDocComment Str MultilineStr RawStr \
+                                IntNumber FloatNumber Char Ident"
+                                      .split_ascii_whitespace()
+                                      .map(|it| it.to_string())
+                                      .collect::<Vec<_>>(),
                            ..Default::default() };
 
     let nodes = grammar.iter().collect::<Vec<_>>();
@@ -714,7 +826,7 @@ fn lower_rule(acc: &mut Vec<Field>,
         Rule::Token(token) => {
             assert!(label.is_none());
             let mut name = grammar[*token].name.clone();
-            if name != "int_number" && name != "string" {
+            if name != "string" {
                 if "[]{}()".contains(&name) {
                     name = format!("'{}'", name);
                 }
@@ -741,7 +853,9 @@ fn lower_rule(acc: &mut Vec<Field>,
             assert!(label.is_none());
             let manually_implemented =
                 matches!(l.as_str(),
-                         "lhs" | "rhs" | "op" | "index" | "base");
+                         "lhs" | "rhs" | "op" | "index" | "base" | "key"
+                         | "value" | "start" | "end" | "cond" | "then_expr"
+                         | "else_expr");
             if manually_implemented {
                 return
             }
@@ -756,7 +870,7 @@ fn lower_rule(acc: &mut Vec<Field>,
     }
 }
 
-// (T (',' T)* ','?)
+// (T (',' T)* ','?) or (T (sep T)*)
 fn lower_comma_list(acc: &mut Vec<Field>,
                     grammar: &Grammar,
                     label: Option<&String>,
@@ -770,15 +884,22 @@ fn lower_comma_list(acc: &mut Vec<Field>,
     let (node, repeat, trailing_comma) = match rule.as_slice() {
         [Rule::Node(node),
          Rule::Rep(repeat),
-         Rule::Opt(trailing_comma)] => (node, repeat, trailing_comma),
+         Rule::Opt(trailing_comma)] => (node, repeat, Some(trailing_comma)),
+        [Rule::Node(node), Rule::Rep(repeat)] => (node, repeat, None),
         _ => return false,
     };
     let repeat = match &**repeat {
         Rule::Seq(it) => it,
         _ => return false,
     };
-    match repeat.as_slice() {
-        [comma, Rule::Node(n)] if comma == &**trailing_comma && n == node => (),
+    match (repeat.as_slice(), trailing_comma) {
+        ([comma, Rule::Node(n)], Some(trailing_comma))
+            if comma == &**trailing_comma && n == node => {}
+        ([separator, Rule::Node(n)], None) if n == node => {
+            if let Rule::Token(token) = separator {
+                acc.push(Field::Token(grammar[*token].name.clone()));
+            }
+        }
         _ => return false,
     }
     let ty = grammar[*node].name.clone();
@@ -1,2 +1,8 @@
 mod ast_src;
+pub mod check_grammar;
+pub mod gen_parser_tests;
 pub mod gen_syntax;
+mod parser_tests;
+
+#[cfg(test)]
+mod tests;
@@ -4,6 +4,8 @@ use clap::Parser;
 enum Command
 {
     GenSyntax,
+    GenParserTests,
+    CheckGrammar,
 }
 
 fn main()
@@ -13,5 +15,16 @@ fn main()
         Command::GenSyntax => {
             xtask::gen_syntax::gen_syntax();
         }
+        Command::GenParserTests => {
+            xtask::gen_parser_tests::gen_parser_tests();
+        }
+        Command::CheckGrammar => {
+            let mismatches = xtask::check_grammar::check_grammar();
+            if mismatches > 0 {
+                eprintln!("{} mismatches with lfr.ungram",
+                          mismatches);
+                std::process::exit(1);
+            }
+        }
     }
 }
@@ -0,0 +1,89 @@
+//! Collects the `// test name` and `// test err name` snippets written next
+//! to the parser functions they exercise.
+
+use std::path::PathBuf;
+
+use walkdir::WalkDir;
+
+pub(crate) struct Test
+{
+    pub(crate) name: String,
+    pub(crate) text: String,
+    /// Whether the snippet should parse without errors.
+    pub(crate) ok:   bool,
+}
+
+/// The snippets of every source file of `lfr-parser`, along with the file
+/// they were found in.
+pub(crate) fn inline_tests() -> Vec<(PathBuf, Test)>
+{
+    let src = cg::ws_path!("crates/lfr-parser/src");
+    let mut files =
+        WalkDir::new(src).into_iter()
+                         .map(|it| it.unwrap().into_path())
+                         .filter(|it| {
+                             it.extension().is_some_and(|it| it == "rs")
+                         })
+                         .collect::<Vec<_>>();
+    files.sort();
+
+    let mut res = vec![];
+    for file in files {
+        let text = cg::read_file(&file);
+        for test in collect_tests(&text) {
+            res.push((file.clone(), test));
+        }
+    }
+    res
+}
+
+fn collect_tests(s: &str) -> Vec<Test>
+{
+    let mut res = vec![];
+    for block in comment_blocks(s) {
+        let (name, ok) = if let Some(name) = block[0].strip_prefix("test err ")
+        {
+            (name, false)
+        }
+        else if let Some(name) = block[0].strip_prefix("test ") {
+            (name, true)
+        }
+        else {
+            continue;
+        };
+
+        let mut text = block[1..].join("\n");
+        text.push('\n');
+        assert!(!text.trim().is_empty(),
+                "test `{}` is empty",
+                name);
+        res.push(Test { name: name.trim().to_string(),
+                        text,
+                        ok });
+    }
+    res
+}
+
+/// Runs of consecutive `//` comment lines, without the leading `// `.
+fn comment_blocks(s: &str) -> Vec<Vec<String>>
+{
+    let mut res = vec![];
+    let mut block = vec![];
+    for line in s.lines() {
+        match line.trim_start().strip_prefix("//") {
+            Some(comment) if !comment.starts_with('/') => {
+                let comment = comment.strip_prefix(' ').unwrap_or(comment);
+                block.push(comment.to_string());
+            }
+            _ => {
+                if !block.is_empty() {
+                    res.push(std::mem::take(&mut block));
+                }
+            }
+        }
+    }
+    if !block.is_empty() {
+        res.push(block);
+    }
+    res
+}
@@ -0,0 +1,93 @@
+//! Checks that the lexer produces the token kinds declared in
+//! [`KINDS_SRC`], that the extracted parser tests are up to date and that
+//! the parser builds the trees `lfr.ungram` describes.
+
+use lfr_parser::lexer::Lexer;
+use lfr_parser::lfr_syntax::SyntaxKind;
+
+use crate::ast_src::KINDS_SRC;
+use crate::check_grammar::check_grammar;
+use crate::gen_parser_tests::inline_test_files;
+
+fn lex(text: &str) -> Vec<SyntaxKind>
+{
+    Lexer::new(text).map(|(kind, _)| kind).collect()
+}
+
+#[test]
+fn keywords_round_trip_through_lexer()
+{
+    for &kw in KINDS_SRC.keywords {
+        let kinds = lex(kw);
+        assert_eq!(kinds.len(),
+                   1,
+                   "keyword `{}` lexed as {:?}",
+                   kw,
+                   kinds);
+        assert_eq!(format!("{:?}", kinds[0]),
+                   format!("{}_KW", kw.to_ascii_uppercase()),
+                   "keyword `{}`",
+                   kw);
+    }
+}
+
+#[test]
+fn contextual_keywords_lex_as_idents()
+{
+    for &kw in KINDS_SRC.contextual_keywords {
+        assert_eq!(lex(kw),
+                   [SyntaxKind::IDENT],
+                   "contextual keyword `{}`",
+                   kw);
+    }
+}
+
+#[test]
+fn puncts_round_trip_through_lexer()
+{
+    for &(punct, name) in KINDS_SRC.punct {
+        let kinds = lex(punct);
+        if punct.len() == 1 {
+            assert_eq!(kinds.len(),
+                       1,
+                       "punct `{}` lexed as {:?}",
+                       punct,
+                       kinds);
+            assert_eq!(format!("{:?}", kinds[0]),
+                       name,
+                       "punct `{}`",
+                       punct);
+        }
+        else {
+            // composite puncts are lexed one char at a time and glued
+            // back together by the parser.
+            let expected = punct.chars()
+                                .map(|c| SyntaxKind::from_char(c).unwrap())
+                                .collect::<Vec<_>>();
+            assert_eq!(kinds, expected,
+                       "composite punct `{}`",
+                       punct);
+        }
+    }
+}
+
+#[test]
+fn inline_parser_tests_are_up_to_date()
+{
+    for (path, text) in inline_test_files() {
+        let actual = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(actual == text,
+                "{} is out of date, run `cargo run -p xtask -- \
+                 gen-parser-tests`",
+                path.display());
+    }
+}
+
+#[test]
+fn parser_trees_match_grammar()
+{
+    assert_eq!(check_grammar(),
+               0,
+               "the mismatches are printed above, run `cargo run -p xtask \
+                -- check-grammar` to see them");
+}
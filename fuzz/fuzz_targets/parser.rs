@@ -0,0 +1,15 @@
+//! Parses arbitrary input, see [`lfr_parser::fuzz::check_parser`].
+//!
+//! Run with `cargo +nightly fuzz run parser` from the repository root, and
+//! shrink what it finds with `cargo +nightly fuzz tmin parser <artifact>`
+//! before adding it to `test_data/parser/fuzz-failures`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        lfr_parser::fuzz::check_parser(text);
+    }
+});
@@ -0,0 +1,18 @@
+//! Reparses arbitrary input after an arbitrary edit, see
+//! [`lfr_parser::fuzz::check_reparse`] for the invariant and
+//! [`lfr_parser::fuzz::reparse_input`] for the input format.
+//!
+//! Run with `cargo +nightly fuzz run reparse` from the repository root.
+
+#![no_main]
+
+use lfr_parser::fuzz::reparse_input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let input = std::str::from_utf8(data).ok()
+                                         .and_then(reparse_input);
+    if let Some((text, edit)) = input {
+        lfr_parser::fuzz::check_reparse(&text, &edit);
+    }
+});